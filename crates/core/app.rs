@@ -1,4 +1,4 @@
-use clap::{App, Arg};
+use clap::{App, Arg, Shell};
 
 pub fn app() -> App<'static, 'static> {
     App::new("naming")
@@ -51,15 +51,158 @@ fn args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .multiple(true)
             .use_delimiter(true)
             // screaming-snake, snake, kebab, camel, pascal, hungarian-notation
-            // S, s, k, c, p, h
-            .possible_values(&["S", "s", "k", "c", "p", "h"])
+            // S, s, k, c, p, h, and an empty value matches nothing.
+            .possible_values(&["S", "s", "k", "c", "p", "h", ""])
             .hide_possible_values(true),
+        Arg::with_name("filter-out")
+            .long("filter-out")
+            .help("Set which formats will be discarded, keep the others")
+            .long_help(FILTER_OUT_HELP)
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true)
+            .possible_values(&["S", "s", "k", "c", "p"])
+            .hide_possible_values(true)
+            .conflicts_with("filter"),
+        Arg::with_name("fuzzy-filter")
+            .long("fuzzy-filter")
+            .value_name("N")
+            .long_help(FUZZY_FILTER_HELP)
+            .help("Let matches with up to N letters in the wrong case pass the filter")
+            .takes_value(true),
+        Arg::with_name("table")
+            .long("table")
+            .help("Output as aligned columns with a header row naming each format")
+            .conflicts_with_all(&["json", "yaml", "csv", "tsv", "regex"]),
+        Arg::with_name("color")
+            .long("color")
+            .value_name("when")
+            .long_help(COLOR_HELP)
+            .help("Colorize the normal output: never, always or auto")
+            .takes_value(true)
+            .possible_values(&["never", "always", "auto"])
+            .default_value("auto"),
+        Arg::with_name("jobs")
+            .short("j")
+            .long("jobs")
+            .value_name("N")
+            .long_help(JOBS_HELP)
+            .help("Read and capture input files with N threads in parallel")
+            .takes_value(true),
+        Arg::with_name("include")
+            .long("include")
+            .value_name("glob")
+            .long_help(INCLUDE_HELP)
+            .help("Only read files matching the glob when walking directories")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+        Arg::with_name("exclude")
+            .long("exclude")
+            .value_name("glob")
+            .help(
+                "Skip files and directories matching the glob \
+                when walking directories",
+            )
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+        Arg::with_name("max-depth")
+            .long("max-depth")
+            .value_name("N")
+            .help(
+                "Walk directories at most N levels deep, \
+                1 means only files directly inside",
+            )
+            .takes_value(true),
+        Arg::with_name("trailing-newline")
+            .long("trailing-newline")
+            .help(
+                "Always end the output with a newline, \
+                by default only output printed to a terminal does",
+            )
+            .overrides_with("no-trailing-newline"),
+        Arg::with_name("no-trailing-newline")
+            .long("no-trailing-newline")
+            .help("Never end the output with a newline")
+            .overrides_with("trailing-newline"),
+        Arg::with_name("output-file")
+            .long("output-file")
+            .value_name("path")
+            .help("Write output to the given file instead of stdout, create or truncate it")
+            .takes_value(true),
+        Arg::with_name("only-ambiguous")
+            .long("only-ambiguous")
+            .long_help(ONLY_AMBIGUOUS_HELP)
+            .help("Only output matches that are valid in multiple formats, with every candidate format")
+            .conflicts_with_all(&["count", "yaml", "csv", "tsv", "regex"]),
+        Arg::with_name("already")
+            .long("already")
+            .value_name("case")
+            .help(
+                "Discard matches that are already in given format (a --output \
+                letter or name), keep the ones that need renaming",
+            )
+            .takes_value(true),
+        Arg::with_name("invalid-only")
+            .long("invalid-only")
+            .help("Only output captured words that are invalid in every format, without converting")
+            .conflicts_with_all(&["filter", "filter-out", "only-ambiguous", "replace"]),
+        Arg::with_name("show-rejected")
+            .long("show-rejected")
+            .long_help(SHOW_REJECTED_HELP)
+            .help("List captured words that are discarded by filter"),
+        Arg::with_name("strip-prefix")
+            .long("strip-prefix")
+            .value_name("str")
+            .long_help(STRIP_PREFIX_HELP)
+            .help("Remove a literal prefix like \"m_\" from matches before recognizing their formats")
+            .takes_value(true),
+        Arg::with_name("hungarian-prefixes")
+            .long("hungarian-prefixes")
+            .long_help(HUNGARIAN_PREFIXES_HELP)
+            .help("Set multi-letter type prefixes stripped by hungarian notation (h) filter")
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true),
         Arg::with_name("eof")
             .short("e")
             .long("eof")
             .long_help(EOF_HELP)
             .help("Set logical EOF string; If occurs, the rest of the input is ignored")
             .takes_value(true),
+        Arg::with_name("input-format")
+            .long("input-format")
+            .value_name("format")
+            .help(
+                "Read input as \"text\" (default) or as \"json\" output \
+                of this tool, whose origin strings are taken as input",
+            )
+            .takes_value(true)
+            .possible_values(&["text", "json"])
+            .default_value("text"),
+        Arg::with_name("skip-binary")
+            .long("skip-binary")
+            .help(
+                "Skip input files that aren't valid UTF-8 with a warning \
+                (default)",
+            )
+            .overrides_with("no-skip-binary"),
+        Arg::with_name("no-skip-binary")
+            .long("no-skip-binary")
+            .help("Fail on input files that aren't valid UTF-8")
+            .overrides_with("skip-binary"),
+        Arg::with_name("null")
+            .short("0")
+            .long("null")
+            .long_help(NULL_HELP)
+            .help("Split stdin into NUL-delimited records, process each separately"),
+        Arg::with_name("files-from")
+            .long("files-from")
+            .value_name("list")
+            .long_help(FILES_FROM_HELP)
+            .help("Read paths of input files from the list file, one per line (\"-\" for stdin)")
+            .takes_value(true),
         Arg::with_name("locator")
             .short("l")
             .long("locator")
@@ -72,6 +215,11 @@ fn args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .takes_value(true)
             .multiple(true)
             .use_delimiter(true),
+        Arg::with_name("normalize")
+            .long("normalize")
+            .long_help(NORMALIZE_HELP)
+            .help("Apply Unicode NFKC normalization to input before capturing, e.g. full-width letters")
+            .conflicts_with("replace"),
         Arg::with_name("output")
             .short("o")
             .long("output")
@@ -79,26 +227,315 @@ fn args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .help("Set which formats that matches will be converted to")
             .takes_value(true)
             .multiple(true)
-            .use_delimiter(true)
-            // can't output hungarian notation format
-            // so there is no "h" value
-            .possible_values(&["S", "s", "k", "c", "p"])
-            .hide_possible_values(true),
+            // can't output hungarian notation format so there is no "h"
+            // value, unknown values are reported by the convertor
+            // as "custom:..." values are also valid.
+            .use_delimiter(true),
+        Arg::with_name("prefix")
+            .long("prefix")
+            .value_name("str")
+            .help("Add a prefix to each converted string, e.g. \"get_\"")
+            .takes_value(true),
+        Arg::with_name("suffix")
+            .long("suffix")
+            .value_name("str")
+            .help("Add a suffix to each converted string, e.g. \"_t\"")
+            .takes_value(true),
+        Arg::with_name("acronyms")
+            .short("a")
+            .long("acronyms")
+            .long_help(ACRONYMS_HELP)
+            .help("Set words that will be fully uppercased in camel, pascal, train and ada case")
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true),
+        Arg::with_name("abbreviations")
+            .long("abbreviations")
+            .value_name("file")
+            .long_help(ABBREVIATIONS_HELP)
+            .help("Replace words with abbreviations listed in the file in converted strings")
+            .takes_value(true),
+        Arg::with_name("merge-acronyms")
+            .long("merge-acronyms")
+            .long_help(MERGE_ACRONYMS_HELP)
+            .help("Treat runs of uppercase letters in camel and pascal case matches as one word (always on)"),
+        Arg::with_name("acronym-style")
+            .long("acronym-style")
+            .long_help(ACRONYM_STYLE_HELP)
+            .help("Set how acronyms in camel and pascal case matches are rendered")
+            .takes_value(true)
+            .possible_values(&["upper", "title", "lower"])
+            .default_value("title"),
+        Arg::with_name("replace")
+            .short("r")
+            .long("replace")
+            .long_help(REPLACE_HELP)
+            .help("Output input text with matches rewritten to given format")
+            .takes_value(true)
+            .conflicts_with_all(&["output", "json", "regex"]),
+        Arg::with_name("unique")
+            .short("u")
+            .long("unique")
+            .long_help(UNIQUE_HELP)
+            .help("Drop matches whose origin duplicates a former one after filtering"),
+        Arg::with_name("sort")
+            .long("sort")
+            .help("Sort matches alphabetically by their origin strings"),
+        Arg::with_name("sort-by")
+            .long("sort-by")
+            .long_help(SORT_BY_HELP)
+            .help("Sort matches alphabetically by their converted strings in given format")
+            .takes_value(true),
+        Arg::with_name("mark-unchanged")
+            .long("mark-unchanged")
+            .help(
+                "Append \"=\" to converted strings that are same as the origin, \
+                or add a \"changed\" object with --json and --yaml",
+            ),
+        Arg::with_name("dry-run")
+            .long("dry-run")
+            .help(
+                "Only output the number of captured words that pass the filter, \
+                without converting or replacing them",
+            ),
+        Arg::with_name("limit")
+            .long("limit")
+            .value_name("N")
+            .help(
+                "Only output the first N matches, after filtering, \
+                deduplicating and sorting",
+            )
+            .takes_value(true),
+        Arg::with_name("sample")
+            .long("sample")
+            .value_name("N")
+            .long_help(SAMPLE_HELP)
+            .help("Only output N randomly selected matches, for spot-checking")
+            .takes_value(true),
+        Arg::with_name("seed")
+            .long("seed")
+            .value_name("N")
+            .help("Set the seed of --sample, the same seed selects the same matches")
+            .takes_value(true)
+            .requires("sample"),
+        Arg::with_name("count")
+            .long("count")
+            .long_help(COUNT_HELP)
+            .help("Output how many matches are in each naming case instead")
+            .conflicts_with_all(&["yaml", "csv", "tsv", "regex", "pretty"]),
+        Arg::with_name("group-by-case")
+            .long("group-by-case")
+            .long_help(GROUP_BY_CASE_HELP)
+            .help("Group matches under headings of their source naming case")
+            .conflicts_with_all(&[
+                "count", "diff", "words", "yaml", "csv", "tsv", "table", "ndjson",
+                "regex", "pretty",
+            ]),
+        Arg::with_name("diff")
+            .long("diff")
+            .help(
+                "Output \"origin -> converted\" lines to the --target case, \
+                only for matches that would change",
+            )
+            .requires("target")
+            .conflicts_with_all(&[
+                "count", "table", "yaml", "csv", "tsv", "regex", "pretty", "words",
+            ]),
+        Arg::with_name("target")
+            .long("target")
+            .value_name("case")
+            .help("Set the target case of --diff, a format letter or name")
+            .takes_value(true)
+            .requires("diff"),
+        Arg::with_name("words")
+            .long("words")
+            .help(
+                "Output the lowercased words of each match instead, one per line, \
+                matches are separated by blank lines",
+            )
+            .conflicts_with_all(&[
+                "count", "table", "yaml", "csv", "tsv", "json", "regex", "pretty",
+            ]),
+        Arg::with_name("stats")
+            .long("stats")
+            .help(
+                "Output a json summary of the run instead: files read, \
+                bytes processed, captures and their count in each naming case",
+            )
+            .conflicts_with_all(&[
+                "count", "table", "yaml", "csv", "tsv", "regex", "pretty",
+            ]),
         Arg::with_name("json")
             .long("json")
             .long_help(JSON_HELP)
             .help("Output in json format"),
+        Arg::with_name("ndjson")
+            .long("ndjson")
+            .long_help(NDJSON_HELP)
+            .help("Output one json object per match per line (newline-delimited json)")
+            .conflicts_with_all(&[
+                "json", "count", "table", "yaml", "csv", "tsv", "regex", "words", "diff",
+                "explain", "stats",
+            ]),
+        Arg::with_name("pretty")
+            .long("pretty")
+            .help("Indent json output with two spaces, works with --json")
+            .requires("json"),
+        Arg::with_name("json-keys")
+            .long("json-keys")
+            .value_name("style")
+            .help(
+                "Spell format names as json keys in snake case \
+                (\"screaming_snake\"), camel case (\"screamingSnake\") \
+                or short letters (\"S\")",
+            )
+            .takes_value(true)
+            .possible_values(&["camel", "snake", "short"])
+            .default_value("snake"),
+        Arg::with_name("yaml")
+            .long("yaml")
+            .long_help(YAML_HELP)
+            .help("Output in yaml format")
+            .conflicts_with_all(&["json", "regex"]),
+        Arg::with_name("csv")
+            .long("csv")
+            .long_help(CSV_HELP)
+            .help("Output in csv format, with a header row")
+            .conflicts_with_all(&["json", "yaml", "regex", "tsv"]),
+        Arg::with_name("tsv")
+            .long("tsv")
+            .long_help(TSV_HELP)
+            .help("Output in tsv format, with a header row")
+            .conflicts_with_all(&["json", "yaml", "regex"]),
+        Arg::with_name("header")
+            .long("header")
+            .help("Start csv, tsv and table outputs with a header row (default)")
+            .overrides_with("no-header"),
+        Arg::with_name("no-header")
+            .long("no-header")
+            .help(
+                "Omit the header row of csv, tsv and table outputs, \
+                e.g. when appending rows to an existing file",
+            )
+            .overrides_with("header"),
         Arg::with_name("regex")
             .long("regex")
             .long_help(REGEX_HELP)
             .help("Output as an OR-joined (e.g. \"x|y|z\") regular expression"),
+        Arg::with_name("regex-anchor")
+            .long("regex-anchor")
+            .help("Wrap the regex output with \"^(...)$\", works with --regex")
+            .requires("regex"),
+        Arg::with_name("regex-word-boundary")
+            .long("regex-word-boundary")
+            .help("Wrap the regex output with \"\\b(...)\\b\", works with --regex")
+            .requires("regex"),
+        Arg::with_name("regex-icase")
+            .long("regex-icase")
+            .help(
+                "Prefix the regex output with \"(?i)\" and drop alternatives \
+                that are same under case folding, works with --regex",
+            )
+            .requires("regex"),
+        Arg::with_name("wrap")
+            .long("wrap")
+            .value_name("N")
+            .help(
+                "Output the regex in extended mode \"(?x)\", with lines \
+                no wider than N columns, works with --regex",
+            )
+            .takes_value(true)
+            .requires("regex"),
+        Arg::with_name("strict")
+            .long("strict")
+            .help(
+                "Exit with code 2 when there is no result, e.g. no match \
+                passes the filter, or nothing is replaced with --replace",
+            ),
+        Arg::with_name("explain")
+            .long("explain")
+            .long_help(EXPLAIN_HELP)
+            .help("Explain why each match is classified as its naming case")
+            .conflicts_with_all(&[
+                "json", "count", "table", "yaml", "csv", "tsv", "regex", "words", "diff",
+            ]),
+        Arg::with_name("strict-case")
+            .long("strict-case")
+            .long_help(STRICT_CASE_HELP)
+            .help("Warn about matches that mix naming cases, like foo_barBaz"),
+        Arg::with_name("timing")
+            .long("timing")
+            .long_help(TIMING_HELP)
+            .help("Print how long each stage took to stderr, e.g. capture"),
+        Arg::with_name("quiet")
+            .short("q")
+            .long("quiet")
+            .help(
+                "Don't print warnings and notices (e.g. skipped files, \
+                rejected words) to stderr, errors are still printed",
+            ),
+        Arg::with_name("no-config")
+            .long("no-config")
+            .long_help(NO_CONFIG_HELP)
+            .help("Ignore the naming.toml config file"),
+        Arg::with_name("version-verbose")
+            .long("version-verbose")
+            .help("Print version, supported output formats and locator modes, one per line"),
+        Arg::with_name("interactive")
+            .short("i")
+            .long("interactive")
+            .help(
+                "Read lines from stdin and print conversions of each line \
+                right away, until EOF (Ctrl-D)",
+            )
+            .conflicts_with_all(&[
+                "files", "files-from", "replace", "dry-run", "stats", "invalid-only",
+                "output-file",
+            ]),
+        Arg::with_name("completions")
+            .long("completions")
+            .value_name("shell")
+            .help("Print the completion script of given shell to stdout, then exit")
+            .takes_value(true)
+            .possible_values(&Shell::variants()),
         Arg::with_name("files")
-            .help("pass file names, or directly pass text via shell pipe")
+            .help(
+                "pass file names (directories are walked recursively), \
+                or directly pass text via shell pipe",
+            )
             .multiple(true),
     ]
         .into_boxed_slice()
 }
 
+const NO_CONFIG_HELP: &str = "\
+Ignore the config file, use only options passed on command line.
+
+By default, the first config file found in this order is used:
+    1. ./naming.toml
+    2. $XDG_CONFIG_HOME/naming/naming.toml
+       ($HOME/.config/naming/naming.toml if XDG_CONFIG_HOME is not set)
+
+Keys in the file are long names of options, values are booleans for
+flags, strings or numbers for single values, and arrays of strings
+for multiple values. For example:
+
+    output = [\"s\", \"k\"]
+    locator = '\\s{}\\s'
+    sort = true
+
+Each element of an array is passed as one value, e.g.
+`include = [\"*.rs\", \"*.go\"]` is same as `--include=*.rs --include=*.go`.
+An unknown key or an invalid value is reported with the file's path.
+
+Precedence (from high to low): options passed on command line,
+options in the config file, default values of options.
+An option passed on command line replaces the file's one as a whole,
+values of a multiple-values option are never merged.
+An option in the file that conflicts with one passed on command line
+is dropped too, e.g. `json = true` is ignored when `--yaml` is passed.
+\n\n";
+
 const FILTER_HELP: &str = "\
 Set which formats will be matched and extracted from input,
 discard other format matches. Default add all formats into
@@ -113,16 +550,211 @@ There are 6 available formats:
     * P -> PascalCase
     * h -> hungarian notation
 
+A camel case match starts with a lowercase letter, has at least one
+uppercase letter after it, and only letters (of any language) and
+digits, e.g. \"pageSize\", \"md5Sum\" and \"nomRéseau\", but not \"page\" (a single word, which is
+still matched by s and k), \"PageSize\" or \"page_Size\".
+
 The last format, hungarian notation means that let the tool
 recognize camel case matches as hungarian notation style,
 strip the first lowercase word (as this identifier's type prefix)
 and keep the remain part for further converting.
 example: \"iPageSize\" --strip\"i\"--> \"PageSize\" --> ...
+It's more lenient than camel case (c), a single lowercase word
+like \"count\" is matched too.
 
 [!]NOTE: Due to the implementation of hungarian notation's matching,
 at most one of the two, hungarian notation (h) and camel case (c)
 can appear in `--filter` option. Pass both of them will let the tool
 exits with non-zero signal.
+
+An empty value (`--filter ''`) matches no format, then nothing is
+extracted, the output is empty (or an empty \"result\" array with
+`--json` option), same as when there is no input at all.
+\n\n";
+
+const FILTER_OUT_HELP: &str = "\
+The opposite of `--filter` option, set which formats will be
+discarded, and keep matches in all the other formats.
+Available formats are same as `--filter` option, except
+the hungarian notation (h).
+
+Note that a match may be valid in several formats, it's discarded
+if any of them is given, e.g. \"abc\" is discarded by \"s\" since it's
+also a valid snake case word, even though it's valid kebab case too.
+
+This option conflicts with `--filter` option.
+\n\n";
+
+const FUZZY_FILTER_HELP: &str = "\
+Tolerate near-miss matches when filtering, a match that is in none
+of the formats still passes the filter, if flipping the case of
+at most N letters makes it valid in one of them:
+
+    $ echo \"user_Id Page_Size\" | naming --filter=s --fuzzy-filter=1
+    user_id USER_ID user_id user-id userId UserId
+
+For screaming snake, snake and kebab case, any letter may be flipped,
+for camel and pascal case (and hungarian notation), only the first
+letter may be, as their other capitals separate words, e.g. \"PageSize\"
+passes \"c\" with N=1. Digits, separators and other characters never
+count as near-misses. When several formats are within reach, the one
+needing the fewest flips wins.
+
+A near-miss is recognized and converted as the corrected word, so the
+origin string in outputs is \"user_id\" rather than \"user_Id\"; with
+`--replace`, the match in the text is rewritten all the same.
+The corrected word is still discarded by `--filter-out` formats.
+
+Defaults to 0, the filter is strict. Conversion is never fuzzy.
+\n\n";
+
+const COLOR_HELP: &str = "\
+Colorize the normal output format (one line per match):
+the origin string is tinted by its source format, and each
+converted string by its target format, one format always has
+the same color. Other output formats are never colorized.
+
+    * never -> never colorize
+    * always -> always colorize, even if output is not a terminal
+    * auto -> colorize when output is a terminal, and the
+              `NO_COLOR` environment variable is not set
+
+Default: auto.
+\n\n";
+
+const INCLUDE_HELP: &str = "\
+Only read files matching the glob when walking directories passed as
+input, can be passed multiple times. Files passed directly are always read.
+In globs, `*` matches any characters except \"/\", `**` matches any
+characters and `?` matches one character. A glob without \"/\" is matched
+against the file name, otherwise against the path relative to the walked
+directory.
+e.g. `naming --include '*.rs' --exclude target .`
+\n\n";
+
+const JOBS_HELP: &str = "\
+Set the number of threads used to read input files and capture
+words from them in parallel, useful for hundreds of files.
+The output is always in the same order as the sequential run.
+Default(1): no parallelism.
+
+[!]NOTE: This option only takes effect when the tool is built with
+the `parallel` feature (`cargo install naming_clt --features parallel`),
+otherwise files are processed one by one.
+\n\n";
+
+const EXPLAIN_HELP: &str = "\
+Instead of converting matches, explain how each one is classified:
+the naming case it's recognized as, the words it's split into,
+the other cases it's also valid in, and why it isn't in the rest
+of screaming snake, snake, kebab, camel and pascal case.
+
+    $ echo \"pageSize\" | naming --explain
+    pageSize
+      case: camel
+      words: page, size
+      also valid: -
+      not screaming_snake: has lowercase letters
+      not snake: has uppercase letters
+      not kebab: has uppercase letters
+      not pascal: doesn't start with an uppercase letter
+
+Useful when a classification surprises you.
+\n\n";
+
+const STRICT_CASE_HELP: &str = "\
+Warn (to stderr) about matches that mix naming cases, which are
+usually left by a half done rename and are dropped as invalid words:
+
+    $ echo \"foo_barBaz foo_bar-baz\" | naming --strict-case
+    naming: mixed case `foo_barBaz`: separated segments with camel humps: `barBaz`.
+    naming: mixed case `foo_bar-baz`: separators `_`, `-` are mixed.
+
+Each distinct match is reported once, the output is not affected.
+\n\n";
+
+const TIMING_HELP: &str = "\
+Print the wall-clock duration of each stage to stderr when it ends,
+to find out where the time goes on large inputs:
+
+    $ naming --timing src/*.rs
+    naming: timing: read       12.402ms
+    naming: timing: capture    85.117ms
+    naming: timing: filter     9.871ms
+    naming: timing: conversion 20.035ms
+
+\"filter\" covers everything between capturing and converting matches,
+e.g. deciding their naming cases, --unique and --sort.
+Some options report fewer stages: `--dry-run` ends after \"filter\",
+`--invalid-only` reports finding invalid words as \"capture\", and
+`--replace` captures, filters and rewrites matches in one pass,
+reported as \"replace\".
+The output is not affected, the clock is not read without this flag.
+\n\n";
+
+const SAMPLE_HELP: &str = "\
+Only output N randomly selected matches (in their input order),
+after filtering and deduplicating, useful for spot-checking naming
+consistency of a huge input. Unlike the deterministic --limit,
+each run selects different matches, unless --seed is given.
+The two can be combined, --limit applies to the sampled matches.
+
+[!]NOTE: This option needs the `sample` feature
+(`cargo install naming_clt --features sample`),
+otherwise it reports an error.
+\n\n";
+
+const ONLY_AMBIGUOUS_HELP: &str = "\
+Only output matches that are genuinely ambiguous, i.e. valid in more
+than one format, like all-lowercase single words (\"abc\" is also a
+snake case and a kebab case word) or all-caps words (\"ABC\" is also
+a screaming snake case word). Useful for finding risky names before
+a large rename.
+
+Instead of converted strings, each line lists every candidate format
+of the match, the first one is the format this tool recognizes it as:
+
+    abc single_word snake kebab
+
+With `--json` option, each element of the \"result\" array has a
+\"candidates\" field listing these formats.
+\n\n";
+
+const SHOW_REJECTED_HELP: &str = "\
+List captured words that are discarded by `--filter` (or `--filter-out`)
+option, e.g. invalid identifiers like \"-invalid_\", which is helpful
+for debugging filter options.
+
+The count and the list of rejected words are printed to stderr,
+so the normal output is untouched. With `--json` option, they are
+put into a \"rejected\" array of the output object instead.
+\n\n";
+
+const STRIP_PREFIX_HELP: &str = "\
+Remove a literal namespace prefix from each match before recognizing
+its format, e.g. with \"m_\", \"m_fooBar\" is treated as \"fooBar\" (camel
+case) and converted from it. Matches that don't start with the prefix
+are kept unchanged. With `--replace` option, the prefix is kept in the
+rewritten text, e.g. \"m_fooBar\" -> \"m_foo_bar\" in snake case.
+
+Unlike hungarian notation (h) in `--filter` option, which strips the
+leading lowercase word, the prefix here is specified by user literally.
+\n\n";
+
+const HUNGARIAN_PREFIXES_HELP: &str = "\
+Set a list of (systems hungarian) type prefixes like \"lpsz,dw\",
+only works with the hungarian notation (h) format in `--filter` option.
+
+By default the tool strips the first lowercase word of a camel case
+match as its type prefix. If the whole lowercase run before the first
+uppercase letter is one of the given prefixes, it is stripped instead:
+
+    $ echo \"lpszUserName\" | naming -f=h --hungarian-prefixes=lpsz
+    UserName USER_NAME user_name user-name userName UserName
+
+Matches that don't start with a given prefix fall back to
+the default behavior.
 \n\n";
 
 const EOF_HELP: &str = "\
@@ -137,6 +769,52 @@ Command above means that output content from first line
 to the line contains given eof-word, to this tool.
 \n\n";
 
+const NULL_HELP: &str = "\
+Split the text read from stdin into NUL-delimited records,
+each record is processed like the content of a separate file.
+Empty records are ignored, and logical EOF (if set) is applied
+to the whole input before splitting. It is useful when the
+input is generated by commands like `find -print0`:
+
+    $ find src -name \"*.rs\" -print0 | naming -0
+
+NOTE: This option has no effect when files are passed,
+except that it splits the path list of `--files-from` option.
+\n\n";
+
+const FILES_FROM_HELP: &str = "\
+Read paths of input files from the given list file, one per line,
+or from stdin if it is \"-\". Listed paths are processed like the
+ones passed as arguments (directories are walked, and the files
+are read in parallel with `--jobs`), after them if both are given.
+It composes with commands like `find` and `fd` without running
+into the length limit of shell arguments:
+
+    $ find src -name \"*.rs\" | naming --files-from -
+
+Pass `--null` option to read a NUL-delimited list instead, for paths
+that contain newlines:
+
+    $ find src -name \"*.rs\" -print0 | naming --files-from - --null
+
+Empty entries are ignored, and it fails if any listed path doesn't
+exist, before reading any file.
+\n\n";
+
+const NORMALIZE_HELP: &str = "\
+Apply Unicode NFKC normalization to the input before capturing
+identifiers, so that compatibility forms copied from rich text are
+recognized as their plain counterparts, e.g. the full-width
+\"ｆｏｏBar\" becomes \"fooBar\", combining marks are composed too.
+Without it, such identifiers are captured partially or misclassified.
+
+Off by default, outputs show the normalized identifiers.
+This option conflicts with `--replace`, which rewrites the text as is.
+
+NOTE: This option needs the `normalize` feature, reinstall with
+`cargo install naming_clt --features normalize` to enable it.
+\n\n";
+
 const LOCATOR_HELP: &str = "\
 Set locator pairs around identifiers, in each pair value,
 the delimiter between prefix and suffix is a pair of brackets \"{}\".
@@ -156,6 +834,71 @@ Any incomplete pair value that couldn't be split
 into two part by space delimiter like \"a\",\"{}a\",\"a{}\"
 will let the tool outputs nothing and exits with non-zero signal.
 
+A value starting with \"regex:\" is used as a whole custom pattern
+instead of a pair, so users can define exactly what counts as an
+identifier, e.g. \"regex:\\$\\w+\" for PHP variables. The first capture
+group of the pattern is taken as the word, or the whole match if the
+pattern has no group. Note that commas delimit multiple values,
+so a pattern can't contain a comma. An invalid pattern will let the
+tool exits with non-zero signal.
+
+A \"string-literal\" value captures words only inside single- or
+double-quoted string literals (backslash-escaped quotes are handled),
+useful for migrating i18n keys or config keys.
+
+A \"markdown-code\" value captures words only inside markdown code,
+i.e. fenced code blocks (``` or ~~~, the language tag on the fence line
+is ignored) and inline code spans (`...`, or ``...`` to contain
+backticks), so ordinary English words in documentation are skipped.
+
+A \"split-glued\" value captures words same as the default value,
+then splits identifiers that were glued together without separators.
+Without hints, it splits before each uppercase letter following a
+lowercase one (\"getUser\" -> \"get\", \"User\"). Hint words can be
+given after a colon, delimited by \"|\", then it only splits before
+each hint following a lowercase letter or digit, e.g. with
+\"split-glued:set\", \"getUserNamesetPassword\" -> \"getUserName\",
+\"setPassword\". This value is opt-in and doesn't affect other values.
+
+By default digits are attached to their neighbors, e.g. \"md5Sum\"
+is one word. A \"split-digits\" value captures words same as the
+default value, then treats each run of digits as a word separator
+and drops it, e.g. \"config2json3yaml\" -> \"config\", \"json\", \"yaml\".
+Use it instead of (not together with) the default value.
+
+A \"qualified\" value captures words same as the default value, along
+with the qualified names they form with \".\" or \"::\", e.g.
+\"obj.method\" and \"ns::Type\" in OO and C++ code, as single words.
+Such a name passes --filter if each of its segments does, and is
+converted segment by segment with the separators kept, e.g.
+\"myObj::getName\" -> \"my_obj::get_name\" in snake case.
+Note that the dot case output (\"d\") also separates words with \".\",
+so \"myObj.getName\" becomes \"my.obj.get.name\", where the segments
+can't be told apart anymore, prefer other output formats with it.
+Use it instead of (not together with) the default value.
+
+A \"path\" value captures words same as the default value, along
+with the path/case words they form with \"/\", e.g. \"user/profile\",
+as single words. Such a word passes --filter if its snake case form
+does, and is converted as that form, e.g. \"user/profile/page\" ->
+\"userProfilePage\" in camel case.
+Use it instead of (not together with) the default value.
+
+A \"mixed-split\" value captures words same as the default value,
+then splits identifiers mixing several formats on every boundary at
+once: separators (\"_\" and \"-\", dropped) and case boundaries, e.g.
+\"XMLData_parser-v2\" -> \"XML\", \"Data\", \"parser\", \"v2\".
+Digits are attached to their neighbors. Use it instead of (not together
+with) the default value, to normalize inconsistently named legacy code.
+
+A \"skip-comments:<lang>\" value captures words same as the default
+value, but not inside comments of the language, to audit actual code
+symbols. Supported languages are c, cpp, java, js, go and rust
+(\"//\" and \"/* */\" comments), shell, python and ruby (\"#\" comments).
+Comment-like sequences inside string literals (e.g. \"http://\") don't
+start a comment, neither does \"#\" right after \"$\" or \"{\" in shell.
+Use it instead of (not together with) the default value.
+
 NOTE:
 Due to the technical limitation, there is no guarantee that
 the order of matches in output (in normal output format, that
@@ -176,19 +919,44 @@ For example:
 
 Commands above piped \"first\\nsecond\" to this tool.
 
+When matches of different values overlap in the text,
+the one from the former value is kept and the latter is dropped.
+For example, with \"string-literal,regex:\\S+\", the match \"'a_b'\"
+of the second value is dropped since it overlaps the \"a_b\"
+inside the string literal.
+
 \n\n";
 
 const OUTPUT_HELP: &str = "\
 Set which naming cases that matches will be converted to.
 
-There are 5 available formats:
+There are 12 available formats:
     * S -> SCREAMING_SNAKE_CASE
     * s -> snake_case
     * k -> kebab-case
     * c -> camelCase
     * P -> PascalCase
+    * t -> Train-Case
+    * d -> dot.case
+    * f -> flatcase
+    * F -> UPPERFLATCASE
+    * a -> Ada_Case
+    * C -> COBOL-CASE
+    * / -> path/case
+
+Each format can also be given by its name, words of which are
+joined by \"-\" or \"_\", e.g. \"screaming-snake\" for S,
+\"upper-flat\" for F, names and letters can be mixed:
 
-Default output all formats in a fix order --
+    $ echo \"pageSize\" | naming -o snake,k,camel
+    pageSize page_size page-size pageSize
+
+NOTE: flatcase and UPPERFLATCASE drop all word boundaries, so
+converting a flatcase identifier back to other formats can't
+recover them. A lowercase word without separators is always
+treated as one single word.
+
+Default output the first 5 formats in a fix order --
 6 words separated by spaces, one line per match in output,
 origin match followed with naming cases of it:
 
@@ -203,6 +971,161 @@ this option, so you can arrange the order of formats
 in line) in output as you wish.
 This will be useful when you want to process the output
 of this tool, like, pass them to `xargs`.
+
+Passing this option narrows the output to the given formats only,
+e.g. \"-o s,k\" outputs the origin match, snake_case and kebab-case.
+Formats t, d, f, F, a, C and / are never in the default output,
+pass them explicitly to get them.
+
+Other combinations can be described by a custom value:
+
+    custom:<separator>:<word case>[:<first word case>]
+
+where word cases are \"lower\", \"upper\" or \"title\" (capitalized),
+the first word follows <word case> unless <first word case> is given,
+the separator can be empty but can't contain \":\" or \",\":
+
+    $ echo \"page_size\" | naming -o \"custom:-:upper,custom::title:lower\"
+    page_size PAGE-SIZE pageSize
+
+The built-in formats are presets of the same mechanism,
+e.g. \"C\" is \"custom:-:upper\" and \"c\" is \"custom::title:lower\".
+A custom value is also the name of the format in structured outputs.
+\n\n";
+
+const ACRONYMS_HELP: &str = "\
+Set a list of acronyms (case insensitive, e.g. \"HTTP,URL,ID\"),
+when converting matches to camelCase, PascalCase, Train-Case or
+Ada_Case, words that appear in this list will be emitted fully uppercased:
+
+    $ echo \"parse_url_id\" | naming --acronyms=url,id --output=c,p
+    parse_url_id parseURLID ParseURLID
+
+NOTE: The first word of camelCase is always kept in lowercase.
+Other output formats are not affected by this option.
+\n\n";
+
+const ABBREVIATIONS_HELP: &str = "\
+Load a word to abbreviation map from the file, each word of a match
+is replaced with its abbreviation before being converted to any format.
+The origin string is kept as is.
+
+The file has one `word=abbreviation` per line (case insensitive,
+both are letters or digits), blank lines and lines starting with
+\"#\" are ignored:
+
+    $ cat abbr.txt
+    config=cfg
+    $ echo \"config_path\" | naming --abbreviations=abbr.txt --output=c
+    config_path cfgPath
+
+NOTE: The conversion is lossy, converting abbreviated strings back
+doesn't recover the origin words.
+\n\n";
+
+const MERGE_ACRONYMS_HELP: &str = "\
+Camel and pascal case matches are always split so that an uppercase
+run is kept as one word, the last letter of a run followed by a
+lowercase letter starts the next word:
+
+    $ echo \"HTTPSConnection getHTTPSPage\" | naming --output=s
+    HTTPSConnection https_connection
+    getHTTPSPage get_https_page
+
+This flag changes nothing, it's kept so that existing scripts
+and config files still work.
+\n\n";
+
+const ACRONYM_STYLE_HELP: &str = "\
+Set how acronyms in camelCase or PascalCase matches (runs of two or more
+uppercase letters, e.g. \"URL\" in \"parseURL\") are rendered by the
+formats that capitalize words (camel, pascal, train and ada case):
+
+    upper: parseURL    title(default): parseUrl    lower: parseurl
+
+With \"lower\", an acronym is glued to its previous word, an acronym
+at the start of a match stays a word. Words in `--acronyms` list
+are always fully uppercased.
+\n\n";
+
+const REPLACE_HELP: &str = "\
+Instead of listing conversions, output the whole input text with
+every match (that passes `--filter`) rewritten to the given format,
+leaving the rest of the text (punctuation, whitespace...) untouched.
+Available values are the same as `--output` option's,
+a format letter or name (e.g. \"s\" or \"snake\"),
+or a custom format (e.g. \"custom:.:lower\").
+
+    $ echo \"int fooBar = bazQux;\" | naming --replace=s
+    int foo_bar = baz_qux;
+
+NOTE: When multiple files are passed, their rewritten contents are
+concatenated in order. Overlapping matches (possible when passing
+multiple locators) are rewritten only once, the leftmost one wins.
+\n\n";
+
+const UNIQUE_HELP: &str = "\
+Drop matches whose origin string duplicates a former match,
+keeping the first-seen one and the order of the rest.
+
+Same words captured from input (even across multiple files) are
+always deduplicated, but different words may become the same
+after filtering, e.g. with `--filter=h`, both \"intSize\" and
+\"strSize\" become \"Size\". This option removes such duplicates.
+
+NOTE: When used with `--count` option, deduplication is applied
+first, so the counts reflect unique origins.
+\n\n";
+
+const SORT_BY_HELP: &str = "\
+Sort matches alphabetically by their converted strings in given
+format, available values are the same as `--output` option's.
+For example, `--sort-by=s` (or `--sort-by=snake`) sorts matches
+by their snake case forms, `--sort-by=custom:.:lower` by their
+dot-separated lowercase forms.
+Use `--sort` option to sort matches by their origin strings instead.
+
+The sort is stable, matches with equal sort keys keep their order
+in input, which makes the output friendly for diff tools.
+\n\n";
+
+const COUNT_HELP: &str = "\
+Instead of converting matches, count them by their (detected)
+source naming case, one case per line, sorted by count:
+
+    $ naming --count src_file
+    camel: 42
+    snake: 17
+    ...
+
+Combine with `--json` option will output a json object:
+
+    {\"counts\":{\"camel\":42,\"snake\":17,...}}
+
+NOTE: Only matches that pass the `--filter` option are counted.
+A word without separators is counted as \"single_word\".
+\n\n";
+
+const GROUP_BY_CASE_HELP: &str = "\
+Group matches by their (detected) source naming case, each group
+starts with a heading, groups are in the order of their first match
+and separated by blank lines:
+
+    $ echo \"pageSize user_id userName\" | naming -o s --group-by-case
+    == camel ==
+    pageSize page_size
+    userName user_name
+
+    == snake ==
+    user_id user_id
+
+Combine with `--json` option will output a json object,
+the match objects are same as the ones in json output:
+
+    {\"camel\":[{\"origin\":\"pageSize\",\"snake\":\"page_size\"},...],
+    \"snake\":[...]}
+
+A word without separators is grouped as \"single_word\".
 \n\n";
 
 const JSON_HELP: &str = "\
@@ -221,12 +1144,66 @@ Output result in json format. the output looks like:
 A \"result\" array field under the root, one element for
 one match's result inside array. Json above is beautified,
 the real output doesn't contain spaces, looks like:
-\"{\"result\":[{...},...]}\".
+\"{\"result\":[{...},...]}\". Pass `--pretty` option to get
+an indented (two spaces) multi-line output instead.
 
 NOTE: The presence of the format fields in result
 depends on whether it is present in `--output` option.
 \n\n";
 
+const NDJSON_HELP: &str = "\
+Output result in newline-delimited json, one object for one match
+per line, same as elements of the \"result\" array in `--json` output:
+
+    {\"origin\":\"pageSize\",\"snake\":\"page_size\",...}
+    {\"origin\":\"user_id\",\"snake\":\"user_id\",...}
+
+Each line is a valid json on its own, so that tools like `jq` can
+process matches line by line, e.g. with `--interactive` sessions.
+Words rejected by `--show-rejected` are reported to stderr.
+\n\n";
+
+const YAML_HELP: &str = "\
+Output result in yaml format. the output looks like:
+
+    result:
+      - origin: \"<origin matched string>\"
+        screaming_snake: \"...\"
+        snake: \"...\"
+        kebab: \"...\"
+        camel: \"...\"
+        pascal: \"...\"
+      - ...
+
+A \"result\" list under the root, one element for one match's
+result inside list. All string values are double-quoted,
+and an empty result is written as \"result: []\".
+
+NOTE: Same as `--json` option, the presence of the format fields
+in result depends on whether it is present in `--output` option.
+This option can't be used with `--json` or `--regex` options.
+\n\n";
+
+const CSV_HELP: &str = "\
+Output result in csv format, which is handy for importing into
+spreadsheets. The first row is a header naming each column,
+followed by one row per match:
+
+    origin,screaming_snake,snake,kebab,camel,pascal
+    <origin match 1>,<SCREAMING_SNAKE_CASE>,<snake_case>,...
+    ...
+
+Fields containing commas, double quotes or line breaks are
+quoted as described in RFC 4180.
+
+NOTE: Same as `--json` option, the presence of the format columns
+depends on whether it is present in `--output` option.
+\n\n";
+
+const TSV_HELP: &str = "\
+Same as `--csv` option, but fields are separated by tabs.
+\n\n";
+
 const REGEX_HELP: &str = "\
 Output result as an OR-joined (e.g. \"x|y|z\") regular expression.
 This is useful when you want to perform further operations involving
@@ -248,6 +1225,49 @@ makes output looks like (beautified):
         ...
     ]}
 
+Regex meta characters (like \".\" in dot.case) in the converted
+strings are escaped. Pass `--regex-anchor` option to wrap the regex
+with \"^(...)$\", or `--regex-word-boundary` option to wrap it with
+\"\\b(...)\\b\", so that it won't match a part of other identifiers.
+Pass `--regex-icase` option to make the regex case-insensitive with
+the inline flag \"(?i)\", alternatives that only differ in case
+(e.g. \"PAGE_SIZE\" and \"page_size\") are kept only once.
+Pass `--wrap=N` option to spread a long regex over lines no wider
+than N columns, it is output in extended mode with the inline flag
+\"(?x)\", where whitespace between alternatives is ignored and
+literal whitespace is escaped, so the pattern matches the same:
+
+    $ echo \"pageSize\" | naming --regex --wrap=22
+    pageSize (?x)
+      PAGE_SIZE|page_size|
+      page-size|pageSize|
+      PageSize
+
+An alternative wider than N columns is put on a line of its own.
+
 NOTE: Same as `--json` option, the presence of the formats inside
 regex depends on whether it is present in `--output` option.
 \n\n";
+
+#[cfg(test)]
+mod app_tests {
+    use naming_clt_lib::NamingCaseKind;
+
+    use super::OUTPUT_HELP;
+
+    #[test]
+    fn output_help_lists_formats_not_in_default_output() {
+        // default output is the first 5 formats.
+        let letters: Vec<&str> = NamingCaseKind::ALL[5..]
+            .iter()
+            .map(NamingCaseKind::letter)
+            .collect();
+        let (others, last) = letters.split_at(letters.len() - 1);
+        let sentence = format!(
+            "Formats {} and {} are never in the default output",
+            others.join(", "),
+            last[0]
+        );
+        assert!(OUTPUT_HELP.contains(&sentence));
+    }
+}
@@ -0,0 +1,357 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::iter;
+use std::path::PathBuf;
+
+use clap::{App, AppSettings, ArgMatches, ErrorKind};
+
+use crate::app;
+
+const CONFIG_FILE_NAME: &str = "naming.toml";
+
+/// A value in config file, mapped to the way it's passed on command line.
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    // `key = true` -> `--key`, `key = false` -> nothing.
+    Flag(bool),
+    // `key = "v"` -> `--key=v`, numbers are taken as strings.
+    Single(String),
+    // `key = ["a", "b"]` -> `--key=a --key=b`
+    List(Vec<String>),
+}
+
+/// Merge options in the config file into given command line matches,
+/// options explicitly passed on command line override the file's ones.
+///
+/// The first config file found is used, in order:
+/// 1. `./naming.toml`
+/// 2. `$XDG_CONFIG_HOME/naming/naming.toml`
+///    (`$HOME/.config/naming/naming.toml` if `XDG_CONFIG_HOME` is not set)
+///
+/// Keys in the file are long names of options (e.g. `output`, `sort-by`).
+/// An option in the file is dropped if it's passed on command line,
+/// or if it conflicts with one passed on command line
+/// (e.g. `json = true` and `--yaml`).
+pub fn merge_config_file(
+    matches: ArgMatches<'static>,
+) -> Result<ArgMatches<'static>, String> {
+    if matches.is_present("no-config") {
+        return Ok(matches);
+    }
+    let path = match find_config_file() {
+        Some(path) => path,
+        None => return Ok(matches),
+    };
+    let in_file = |err: String| format!("naming: {}: {}", path.display(), err);
+    let content =
+        fs::read_to_string(&path).map_err(|err| in_file(err.to_string()))?;
+    let entries = parse(&content).map_err(in_file)?;
+
+    // insert right after the program name, so that they are never
+    // taken as positional arguments (e.g. after "--").
+    let mut program: Vec<OsString> = env::args_os().collect();
+    let command_line = program.split_off(1.min(program.len()));
+
+    let mut config_args = Vec::new();
+    for (key, value) in entries {
+        check_entry(&key, &value).map_err(in_file)?;
+        let args = to_args(&key, value);
+        // defaults (e.g. `--color=auto`) don't count as occurrences.
+        // options that override each other (e.g. `header` and `no-header`)
+        // need no check, the later one (on command line) wins.
+        if matches.occurrences_of(&key) == 0
+            && !conflicts_with(&program, &args, &command_line)
+        {
+            config_args.extend(args);
+        }
+    }
+    if config_args.is_empty() {
+        return Ok(matches);
+    }
+
+    let args = program.into_iter().chain(config_args).chain(command_line);
+    // options on command line are valid alone, so the error is caused
+    // by options in the file, e.g. two of them conflict with each other.
+    quiet_app()
+        .get_matches_from_safe(args)
+        .map_err(|err| in_file(reason_of(&err)))
+}
+
+/// Check an entry alone, so that an unknown key or an invalid value
+/// is reported with the path of config file, instead of by clap.
+/// Errors depending on other options (e.g. `--regex-icase`
+/// requires `--regex`) are left to the merged command line.
+fn check_entry(key: &str, value: &Value) -> Result<(), String> {
+    let flag = vec![OsString::from(format!("--{}", key))];
+    let err = match value {
+        // `key = false` adds nothing, but the key should still be known.
+        Value::Flag(false) => match parse_alone(flag) {
+            Err(err) if err.kind == ErrorKind::EmptyValue => return Ok(()),
+            result => result.err(),
+        },
+        Value::Flag(true) => parse_alone(flag).err(),
+        value => match parse_alone(to_args(key, value.clone())) {
+            // clap ignores the value given to a flag (e.g. `--sort=x`).
+            Ok(()) if parse_alone(flag).is_ok() => {
+                return Err(format!("key `{}` expects a boolean.", key))
+            }
+            result => result.err(),
+        },
+    };
+    match err.map(|err| (err.kind, err)) {
+        None => Ok(()),
+        Some((ErrorKind::UnknownArgument, _)) => {
+            Err(format!("unknown key `{}`.", key))
+        }
+        Some((ErrorKind::HelpDisplayed, _))
+        | Some((ErrorKind::VersionDisplayed, _)) => {
+            Err(format!("key `{}` can't be set in config file.", key))
+        }
+        Some((ErrorKind::MissingRequiredArgument, _)) => Ok(()),
+        Some((_, err)) => Err(format!("key `{}`: {}", key, reason_of(&err))),
+    }
+}
+
+fn parse_alone(args: Vec<OsString>) -> Result<(), clap::Error> {
+    let argv = iter::once(OsString::from("naming")).chain(args);
+    quiet_app().get_matches_from_safe(argv).map(|_| ())
+}
+
+/// Whether given options from config file
+/// conflict with the ones passed on command line.
+fn conflicts_with(
+    program: &[OsString],
+    args: &[OsString],
+    command_line: &[OsString],
+) -> bool {
+    let argv = program.iter().chain(args).chain(command_line).cloned();
+    match quiet_app().get_matches_from_safe(argv) {
+        Err(err) => err.kind == ErrorKind::ArgumentConflict,
+        Ok(_) => false,
+    }
+}
+
+/// The app that reports errors to caller without colors.
+fn quiet_app() -> App<'static, 'static> {
+    app::app().setting(AppSettings::ColorNever)
+}
+
+/// The first line of clap's error message, without "error: " prefix.
+fn reason_of(err: &clap::Error) -> String {
+    let line = err.message.lines().next().unwrap_or_default();
+    line.trim_start_matches("error: ").to_string()
+}
+
+fn find_config_file() -> Option<PathBuf> {
+    let in_current_dir = PathBuf::from(CONFIG_FILE_NAME);
+    let config_home =
+        env::var_os("XDG_CONFIG_HOME").map(PathBuf::from).or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))
+        });
+    let in_config_home =
+        config_home.map(|dir| dir.join("naming").join(CONFIG_FILE_NAME));
+
+    Some(in_current_dir)
+        .into_iter()
+        .chain(in_config_home)
+        .find(|path| path.is_file())
+}
+
+fn to_args(key: &str, value: Value) -> Vec<OsString> {
+    match value {
+        Value::Flag(true) => vec![format!("--{}", key).into()],
+        Value::Flag(false) => Vec::new(),
+        Value::Single(value) => vec![format!("--{}={}", key, value).into()],
+        // one occurrence per value, some options (e.g. `--include`)
+        // don't split a value by commas.
+        Value::List(values) => values
+            .iter()
+            .map(|value| format!("--{}={}", key, value).into())
+            .collect(),
+    }
+}
+
+/// Parse the subset of TOML that config file needs:
+/// `key = value` lines where value is a boolean, a number,
+/// a string or an array of strings, and `#` comments.
+fn parse(content: &str) -> Result<Vec<(String, Value)>, String> {
+    let mut entries = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = parse_line(line)
+            .map_err(|err| format!("line {}: {}", index + 1, err))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn parse_line(line: &str) -> Result<(String, Value), String> {
+    if line.starts_with('[') {
+        return Err("tables are not supported.".to_string());
+    }
+    let (key, value) = line
+        .split_once('=')
+        .ok_or_else(|| "expect `key = value`.".to_string())?;
+    let key = key.trim();
+    let is_bare_key = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !is_bare_key {
+        return Err(format!("invalid key `{}`.", key));
+    }
+
+    let mut chars = value.trim().chars().peekable();
+    let value = match chars.peek() {
+        Some('[') => {
+            chars.next();
+            let mut values = Vec::new();
+            loop {
+                skip_whitespace(&mut chars);
+                match chars.peek() {
+                    Some(']') => {
+                        chars.next();
+                        break;
+                    }
+                    Some('"') | Some('\'') => {
+                        values.push(parse_string(&mut chars)?)
+                    }
+                    _ => return Err("expect an array of strings.".to_string()),
+                }
+                // each string is followed by a comma or the end of array.
+                skip_whitespace(&mut chars);
+                match chars.next() {
+                    Some(',') => continue,
+                    Some(']') => break,
+                    _ => return Err("expect an array of strings.".to_string()),
+                }
+            }
+            Value::List(values)
+        }
+        Some('"') | Some('\'') => Value::Single(parse_string(&mut chars)?),
+        _ => {
+            let token: String =
+                chars.by_ref().take_while(|c| !c.is_whitespace()).collect();
+            match token.as_str() {
+                "true" => Value::Flag(true),
+                "false" => Value::Flag(false),
+                number if number.parse::<f64>().is_ok() => {
+                    Value::Single(number.to_string())
+                }
+                other => return Err(format!("invalid value `{}`.", other)),
+            }
+        }
+    };
+
+    // only a comment can follow the value.
+    skip_whitespace(&mut chars);
+    match chars.next() {
+        None | Some('#') => Ok((key.to_string(), value)),
+        Some(c) => Err(format!("unexpected `{}` after value.", c)),
+    }
+}
+
+fn skip_whitespace<I: Iterator<Item = char>>(
+    chars: &mut std::iter::Peekable<I>,
+) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Parse a basic ("...", with backslash escapes)
+/// or a literal ('...', as is) string.
+fn parse_string<I: Iterator<Item = char>>(
+    chars: &mut std::iter::Peekable<I>,
+) -> Result<String, String> {
+    let quote = chars.next();
+    let mut string = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            c if Some(c) == quote => return Ok(string),
+            '\\' if quote == Some('"') => match chars.next() {
+                Some('n') => string.push('\n'),
+                Some('t') => string.push('\t'),
+                Some(c @ '"') | Some(c @ '\\') => string.push(c),
+                _ => return Err("invalid escape in string.".to_string()),
+            },
+            c => string.push(c),
+        }
+    }
+    Err("unclosed string.".to_string())
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::{check_entry, parse, to_args, Value};
+
+    #[test]
+    fn parse_supported_values() {
+        let content = r#"
+# defaults for this project
+output = ["s", "k"]
+locator = '\s{}\s'
+sort = true
+unique = false  # comment after value
+jobs = 4
+"#;
+        let expect = vec![
+            ("output".to_string(), Value::List(vec!["s".into(), "k".into()])),
+            ("locator".to_string(), Value::Single(r"\s{}\s".into())),
+            ("sort".to_string(), Value::Flag(true)),
+            ("unique".to_string(), Value::Flag(false)),
+            ("jobs".to_string(), Value::Single("4".into())),
+        ];
+        assert_eq!(parse(content).unwrap(), expect);
+    }
+
+    #[test]
+    fn parse_errors_have_line_number() {
+        assert_eq!(
+            parse("a = 1\n[table]").unwrap_err(),
+            "line 2: tables are not supported."
+        );
+        assert_eq!(parse("a = \"b").unwrap_err(), "line 1: unclosed string.");
+        assert_eq!(parse("a = b").unwrap_err(), "line 1: invalid value `b`.");
+        assert_eq!(parse("a").unwrap_err(), "line 1: expect `key = value`.");
+        assert_eq!(
+            parse(r#"a = ["b" "c"]"#).unwrap_err(),
+            "line 1: expect an array of strings."
+        );
+    }
+
+    #[test]
+    fn check_entries_alone() {
+        assert!(check_entry("sort", &Value::Flag(true)).is_ok());
+        assert!(check_entry("output", &Value::Flag(false)).is_ok());
+        // requires `--regex`, which may be passed on command line.
+        assert!(check_entry("regex-icase", &Value::Flag(true)).is_ok());
+        assert_eq!(
+            check_entry("sorted", &Value::Flag(false)).unwrap_err(),
+            "unknown key `sorted`."
+        );
+        assert!(check_entry("color", &Value::Single("x".into()))
+            .unwrap_err()
+            .starts_with("key `color`: "));
+        assert_eq!(
+            check_entry("sort", &Value::Single("x".into())).unwrap_err(),
+            "key `sort` expects a boolean."
+        );
+        assert_eq!(
+            check_entry("help", &Value::Flag(true)).unwrap_err(),
+            "key `help` can't be set in config file."
+        );
+    }
+
+    #[test]
+    fn values_to_args() {
+        assert_eq!(to_args("sort", Value::Flag(true)), vec!["--sort"]);
+        assert!(to_args("sort", Value::Flag(false)).is_empty());
+        assert_eq!(
+            to_args("output", Value::List(vec!["s".into(), "k".into()])),
+            vec!["--output=s", "--output=k"]
+        );
+    }
+}
@@ -1,22 +1,62 @@
-use std::error::Error;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::num::NonZeroUsize;
 use std::process;
+use std::str::FromStr;
+use std::time::Instant;
 
-use clap::ArgMatches;
+use clap::{ArgMatches, Shell};
 
 use naming_clt_lib::*;
 
 mod app;
+mod config;
+
+/// Exit code when `--strict` is passed and there is no result.
+const EXIT_NO_RESULT: i32 = 2;
 
 fn main() {
-    match operate(app::app().get_matches()) {
-        Ok(output) => {
-            if is_atty_stdout() {
-                println!("{}", output);
-            } else {
-                print!("{}", output);
+    let matches = match config::merge_config_file(app::app().get_matches()) {
+        Ok(matches) => matches,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+    if matches.is_present("version-verbose") {
+        println!("{}", version_verbose());
+        process::exit(0);
+    }
+    if let Some(shell) = matches.value_of("completions") {
+        // possible values are checked by clap.
+        app::app().gen_completions_to(
+            "naming",
+            shell.parse::<Shell>().unwrap(),
+            &mut io::stdout(),
+        );
+        process::exit(0);
+    }
+    if matches.is_present("interactive") {
+        match interact(&matches) {
+            Ok(_) => process::exit(0),
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
             }
-            process::exit(0);
         }
+    }
+    let output_file = matches.value_of("output-file").map(String::from);
+    let trailing_newline = trailing_newline(&matches);
+    let strict = matches.is_present("strict");
+    let result = operate(matches).and_then(|(output, no_result)| {
+        write_output(&output, output_file.as_deref(), trailing_newline)
+            .map(|_| no_result)
+    });
+    match result {
+        Ok(true) if strict => process::exit(EXIT_NO_RESULT),
+        Ok(_) => process::exit(0),
         Err(err) => {
             eprintln!("{}", err);
             process::exit(1);
@@ -24,71 +64,612 @@ fn main() {
     };
 }
 
+/// Answer user's `--trailing-newline` and `--no-trailing-newline` options,
+/// None if neither is passed.
+fn trailing_newline(matches: &ArgMatches) -> Option<bool> {
+    if matches.is_present("trailing-newline") {
+        Some(true)
+    } else if matches.is_present("no-trailing-newline") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Write the output to given file (created or truncated),
+/// or print it to stdout if no file is given.
+/// Without an explicit `trailing_newline`, only output printed
+/// to a terminal gets the trailing newline.
+fn write_output(
+    output: &str,
+    file: Option<&str>,
+    trailing_newline: Option<bool>,
+) -> Result<(), NamingError> {
+    let newline = match trailing_newline {
+        Some(newline) => newline,
+        None => file.is_none() && is_atty_stdout(),
+    };
+    let newline = if newline { "\n" } else { "" };
+    match file {
+        Some(path) => {
+            fs::write(path, output.to_string() + newline).map_err(|err| {
+                NamingError::IoError { path: path.to_string(), source: err }
+            })
+        }
+        None => {
+            print!("{}{}", output, newline);
+            Ok(())
+        }
+    }
+}
+
+/// Answer user's `--version-verbose` option, list supported
+/// `--output` formats and `--locator` modes, one per line:
+/// naming <version>
+/// output <letter> <name>
+/// ...
+/// locator <mode>
+/// ...
+fn version_verbose() -> String {
+    let mut lines = vec![format!("naming {}", env!("CARGO_PKG_VERSION"))];
+    lines.extend(
+        ConversionRegistry::default()
+            .formats()
+            .map(|(key, format)| format!("output {} {}", key, format.name())),
+    );
+    lines.extend(LOCATOR_MODES.iter().map(|mode| format!("locator {}", mode)));
+    lines.join("\n")
+}
+
 fn is_atty_stdout() -> bool {
     atty::is(atty::Stream::Stdout)
 }
 
-/// A wrapper that does everything from user input to output.
-fn operate(matches: ArgMatches) -> Result<String, Box<dyn Error>> {
-    let text = get_text_from_input(&matches)?;
-    let convertor = wrap_text_with_converter(&matches, text)?;
-    output_as_string(matches, convertor)
+/// A wrapper that does everything from user input to output,
+/// along with whether there is no result (for `--strict` option):
+/// no match passes the filter, or no word is replaced with `--replace`.
+fn operate(matches: ArgMatches) -> Result<(String, bool), NamingError> {
+    let mut timer = Timer::new(&matches);
+    let (text, input_stats) = get_text_from_input(&matches)?;
+    timer.lap("read");
+    if matches.is_present("dry-run") {
+        let count = count_filtered_words(&matches, text, &mut timer)?;
+        return Ok((count.to_string(), count == 0));
+    }
+    if let Some(target) = matches.value_of("replace") {
+        let origin = text.concat();
+        let replaced = replace_words_in_text(&matches, target, text)?;
+        // matches are captured, filtered and rewritten in one pass.
+        timer.lap("replace");
+        let no_result = replaced == origin;
+        return Ok((replaced, no_result));
+    }
+    if matches.is_present("invalid-only") {
+        let words = find_invalid_words(&matches, text)?;
+        timer.lap("capture");
+        let output = if matches.is_present("json") {
+            words_to_json(&words)
+        } else {
+            words.join("\n")
+        };
+        return Ok((output, words.is_empty()));
+    }
+    let convertor = wrap_text_with_converter(&matches, text, &mut timer)?;
+    let no_result = convertor.is_empty();
+    let output = if matches.is_present("stats") {
+        convertor.into_stats(&input_stats)
+    } else {
+        output_as_string(&matches, convertor)?
+    };
+    timer.lap("conversion");
+    Ok((output, no_result))
+}
+
+/// Answer user's `--timing` option, report the wall-clock duration
+/// of each stage to stderr when it ends.
+/// The clock is never read when the option is off.
+struct Timer {
+    last: Option<Instant>,
+}
+
+impl Timer {
+    fn new(matches: &ArgMatches) -> Timer {
+        let last = if matches.is_present("timing") {
+            Some(Instant::now())
+        } else {
+            None
+        };
+        Timer { last }
+    }
+
+    /// End the current stage (started when the previous one ended).
+    fn lap(&mut self, stage: &str) {
+        if let Some(last) = self.last {
+            let now = Instant::now();
+            eprintln!("naming: timing: {:<10} {:.3?}", stage, now - last);
+            self.last = Some(now);
+        }
+    }
+}
+
+/// Answer user's `--interactive` option, convert each line read
+/// from stdin with the session's options, until EOF (Ctrl-D).
+/// A failure on one line is reported and doesn't end the session.
+fn interact(matches: &ArgMatches) -> Result<(), NamingError> {
+    let prompt = is_atty_stdin();
+    let io_error =
+        |err| NamingError::IoError { path: "stdin".to_string(), source: err };
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        if prompt {
+            print!("naming> ");
+            io::stdout().flush().map_err(io_error)?;
+        }
+        let line = match lines.next() {
+            Some(line) => line.map_err(io_error)?,
+            None => break,
+        };
+        let mut timer = Timer::new(matches);
+        let output = wrap_text_with_converter(matches, vec![line], &mut timer)
+            .and_then(|convertor| output_as_string(matches, convertor));
+        timer.lap("conversion");
+        match output {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => println!("{}", output),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+    // leave the prompt line after Ctrl-D.
+    if prompt {
+        println!();
+    }
+    Ok(())
 }
 
+/// Read the text and count its size for `--stats` option.
 fn get_text_from_input(
     matches: &ArgMatches,
-) -> Result<Vec<String>, Box<dyn Error>> {
+) -> Result<(Vec<String>, InputStats), NamingError> {
     let eof = matches.value_of("eof");
-    let text = match matches.values_of_lossy("files") {
+    let mut paths = matches.values_of_lossy("files");
+    if let Some(list) = matches.value_of("files-from") {
+        let listed = read_file_list(list, matches.is_present("null"))?;
+        paths.get_or_insert_with(Vec::new).extend(listed);
+    }
+    let files = match paths {
+        Some(paths) => Some(build_dir_walker(matches)?.expand(&paths)?),
+        None => None,
+    };
+    let text = match &files {
         None => {
             if is_atty_stdin() {
-                return Err(
-                    "naming: no input was found. Enter -h or --help for help information.".into()
-                );
+                return Err(NamingError::NoInput);
+            } else if matches.is_present("null") {
+                read_nul_delimited_from_std_in(eof)?
             } else {
                 vec![read_from_std_in(eof)?]
             }
         }
-        Some(files) => read_from_files(&files, eof)?,
+        Some(files) if matches.is_present("no-skip-binary") => {
+            read_from_files_in_parallel(files, eof, jobs(matches)?)?
+        }
+        Some(files) => read_skipping_binary_files(
+            files,
+            eof,
+            jobs(matches)?,
+            matches.is_present("quiet"),
+        )?,
+    };
+    let stats = InputStats::of(&text, files.map_or(0, |_| text.len()));
+    let text = if matches.value_of("input-format") == Some("json") {
+        text.iter()
+            .map(|t| origins_from_json(t))
+            .collect::<Result<Vec<String>, NamingError>>()?
+    } else {
+        text
     };
+    Ok((text, stats))
+}
+
+/// Answer user's `--skip-binary` option (on by default), warn about
+/// each file that isn't valid UTF-8 and skip it, then report the number.
+/// Files are skipped silently with `--quiet` option.
+fn read_skipping_binary_files(
+    files: &[String],
+    eof: Option<&str>,
+    jobs: usize,
+    quiet: bool,
+) -> Result<Vec<String>, NamingError> {
+    let (text, skipped) = read_text_files_in_parallel(files, eof, jobs)?;
+    if !skipped.is_empty() && !quiet {
+        for file in &skipped {
+            eprintln!("naming: {}: skipped, not a UTF-8 text file.", file);
+        }
+        eprintln!("naming: {} binary file(s) skipped.", skipped.len());
+    }
     Ok(text)
 }
 
+/// Answer user's `--include`, `--exclude` and `--max-depth` options.
+fn build_dir_walker(matches: &ArgMatches) -> Result<DirWalker, NamingError> {
+    let max_depth = match matches.value_of("max-depth") {
+        None => None,
+        Some(value) => match value.parse::<usize>() {
+            Ok(max_depth) => Some(max_depth),
+            Err(_) => {
+                return Err(invalid_value(
+                    "max-depth",
+                    value,
+                    "a non-negative integer",
+                ))
+            }
+        },
+    };
+    Ok(DirWalker::new(
+        matches.values_of_lossy("include"),
+        matches.values_of_lossy("exclude"),
+        max_depth,
+    ))
+}
+
+/// Answer user's `--jobs` option, default to 1 (no parallelism).
+fn jobs(matches: &ArgMatches) -> Result<usize, NamingError> {
+    match matches.value_of("jobs") {
+        None => Ok(1),
+        Some(value) => match value.parse::<usize>() {
+            Ok(jobs) if jobs > 0 => Ok(jobs),
+            _ => Err(invalid_value("jobs", value, "a positive integer")),
+        },
+    }
+}
+
+/// Answer user's `--wrap` option, zero columns is rejected.
+fn regex_wrap(matches: &ArgMatches) -> Result<Option<usize>, NamingError> {
+    let width: Option<NonZeroUsize> =
+        parse_value(matches, "wrap", "a positive integer")?;
+    Ok(width.map(NonZeroUsize::get))
+}
+
+/// Answer user's `--color` option, "auto" colorizes the output
+/// only when it's printed to a terminal and `NO_COLOR` isn't set.
+fn use_color(matches: &ArgMatches) -> bool {
+    match matches.value_of("color") {
+        Some("always") => true,
+        Some("never") => false,
+        _ => {
+            is_atty_stdout()
+                && !matches.is_present("output-file")
+                && env::var_os("NO_COLOR").is_none()
+        }
+    }
+}
+
 fn is_atty_stdin() -> bool {
     atty::is(atty::Stream::Stdin)
 }
 
+/// Answer user's `--strict-case` option, report each distinct identifier
+/// that mixes naming cases (e.g. "foo_barBaz") to stderr.
+fn warn_mixed_cases(words: &[String]) {
+    let mut reported = HashSet::new();
+    for word in words {
+        if let Some(conflict) = case_conflict(word) {
+            if reported.insert(word) {
+                eprintln!("naming: mixed case `{}`: {}.", word, conflict);
+            }
+        }
+    }
+}
+
 fn wrap_text_with_converter(
     matches: &ArgMatches,
     text: Vec<String>,
-) -> Result<Convertor, Box<dyn Error>> {
+    timer: &mut Timer,
+) -> Result<Convertor, NamingError> {
     let option = |tag: &str| matches.values_of_lossy(tag);
 
+    let filter = build_filter(matches)?;
+    let words = build_captor(matches)?
+        .capture_words_in_parallel(text, jobs(matches)?)?;
+    timer.lap("capture");
+    if matches.is_present("strict-case") && !matches.is_present("quiet") {
+        warn_mixed_cases(&words);
+    }
+    let rejected = if matches.is_present("show-rejected") {
+        Some(filter.rejected_words(&words))
+    } else {
+        None
+    };
+
     // text (String) --Captor--> words (Vec<String>)
     // --Filter--> intermediate type instances (Vec<NamingCase>)
     // --> Convertor (ready to convert itself into different format outputs)
-    let convertor = Convertor::new(
-        option("output"),
-        Filter::new(option("filter"))?.to_naming_cases_from(
-            Captor::new(option("locator"))?.capture_words(text),
-        ),
-    );
+    let convertor = Convertor::from_words(option("output"), words, &filter)
+        .with_acronyms(option("acronyms"))
+        .with_acronym_style(acronym_style(matches)?)
+        .with_json_keys(
+            matches.value_of("json-keys").unwrap_or("snake").parse()?,
+        )
+        .with_acronyms_merged(matches.is_present("merge-acronyms"))
+        .with_abbreviations(match matches.value_of("abbreviations") {
+            Some(path) => read_abbreviations(path)?,
+            None => HashMap::new(),
+        })
+        .with_affixes(matches.value_of("prefix"), matches.value_of("suffix"))
+        .with_regex_bounds(
+            matches.is_present("regex-anchor"),
+            matches.is_present("regex-word-boundary"),
+        )
+        .with_regex_icase(matches.is_present("regex-icase"))
+        .with_regex_wrap(regex_wrap(matches)?)
+        .with_unchanged_marked(matches.is_present("mark-unchanged"))
+        .with_header(!matches.is_present("no-header"))
+        .validated()?;
+
+    // rejected words go into json output, or to stderr otherwise
+    // (unless `--quiet` is passed).
+    let json_output =
+        matches.is_present("json") && !matches.is_present("count");
+    let convertor = match rejected {
+        Some(rejected) if json_output => convertor.with_rejected(rejected),
+        Some(_) if matches.is_present("quiet") => convertor,
+        Some(rejected) => {
+            eprintln!(
+                "naming: {} word(s) rejected by filter: {}",
+                rejected.len(),
+                rejected.join(" ")
+            );
+            convertor
+        }
+        None => convertor,
+    };
+
+    let convertor = if matches.is_present("only-ambiguous") {
+        convertor.retain_ambiguous()
+    } else {
+        convertor
+    };
+
+    let convertor = match matches.value_of("already") {
+        Some(target) => convertor.retain_not_already(target.parse()?),
+        None => convertor,
+    };
+
+    let convertor = if matches.is_present("unique") {
+        convertor.dedup_origins()
+    } else {
+        convertor
+    };
+
+    let convertor = match sample_size(matches)? {
+        Some(size) => sample(convertor, size, matches)?,
+        None => convertor,
+    };
+
+    let convertor =
+        if matches.is_present("sort") || matches.is_present("sort-by") {
+            convertor.sort_by(matches.value_of("sort-by"))?
+        } else {
+            convertor
+        };
+
+    let convertor = match limit(matches)? {
+        Some(limit) => convertor.limit(limit),
+        None => convertor,
+    };
+    timer.lap("filter");
     Ok(convertor)
 }
 
+/// Answer user's `--locator` and `--normalize` options.
+fn build_captor(matches: &ArgMatches) -> Result<Captor, NamingError> {
+    let captor = Captor::new(matches.values_of_lossy("locator"))?;
+    normalized(captor, matches.is_present("normalize"))
+}
+
+#[cfg(feature = "normalize")]
+fn normalized(captor: Captor, normalize: bool) -> Result<Captor, NamingError> {
+    Ok(captor.with_normalization(normalize))
+}
+
+#[cfg(not(feature = "normalize"))]
+fn normalized(captor: Captor, normalize: bool) -> Result<Captor, NamingError> {
+    if normalize {
+        return Err(NamingError::FeatureDisabled {
+            option: "normalize".to_string(),
+            feature: "normalize".to_string(),
+        });
+    }
+    Ok(captor)
+}
+
+/// Answer user's `--acronym-style` option, default to "title".
+fn acronym_style(matches: &ArgMatches) -> Result<AcronymStyle, NamingError> {
+    matches.value_of("acronym-style").unwrap_or("title").parse()
+}
+
+/// Answer user's `--limit` option.
+fn limit(matches: &ArgMatches) -> Result<Option<usize>, NamingError> {
+    parse_value(matches, "limit", "a non-negative integer")
+}
+
+/// Answer user's `--sample` option.
+fn sample_size(matches: &ArgMatches) -> Result<Option<usize>, NamingError> {
+    parse_value(matches, "sample", "a non-negative integer")
+}
+
+/// Answer user's `--sample` and `--seed` options.
+#[cfg(feature = "sample")]
+fn sample(
+    convertor: Convertor,
+    size: usize,
+    matches: &ArgMatches,
+) -> Result<Convertor, NamingError> {
+    let seed = parse_value(matches, "seed", "a non-negative integer")?;
+    Ok(convertor.sample(size, seed))
+}
+
+#[cfg(not(feature = "sample"))]
+fn sample(
+    _: Convertor,
+    _: usize,
+    _: &ArgMatches,
+) -> Result<Convertor, NamingError> {
+    Err(NamingError::FeatureDisabled {
+        option: "sample".to_string(),
+        feature: "sample".to_string(),
+    })
+}
+
+/// Parse value of the option, `None` if user doesn't enter it.
+fn parse_value<T: FromStr>(
+    matches: &ArgMatches,
+    option: &str,
+    expected: &str,
+) -> Result<Option<T>, NamingError> {
+    match matches.value_of(option) {
+        None => Ok(None),
+        Some(value) => match value.parse::<T>() {
+            Ok(parsed) => Ok(Some(parsed)),
+            Err(_) => Err(invalid_value(option, value, expected)),
+        },
+    }
+}
+
+fn invalid_value(option: &str, value: &str, expected: &str) -> NamingError {
+    NamingError::InvalidOptionValue {
+        option: option.to_string(),
+        value: value.to_string(),
+        expected: expected.to_string(),
+    }
+}
+
+/// Answer user's `--filter`, `--filter-out`, `--strip-prefix`
+/// and `--hungarian-prefixes` options.
+fn build_filter(matches: &ArgMatches) -> Result<Filter, NamingError> {
+    let option = |tag: &str| matches.values_of_lossy(tag);
+    Ok(Filter::new(option("filter"))?
+        .excluding(option("filter-out"))
+        .with_stripped_prefix(matches.value_of("strip-prefix"))
+        .with_hungarian_prefixes(option("hungarian-prefixes"))
+        .with_tolerance(fuzzy_filter(matches)?))
+}
+
+/// Answer user's `--fuzzy-filter` option, default to 0 (strict).
+fn fuzzy_filter(matches: &ArgMatches) -> Result<usize, NamingError> {
+    let tolerance =
+        parse_value(matches, "fuzzy-filter", "a non-negative integer")?;
+    Ok(tolerance.unwrap_or(0))
+}
+
+/// Answer user's `--dry-run` option, output only the number of
+/// captured words that pass the filter, without converting them.
+fn count_filtered_words(
+    matches: &ArgMatches,
+    text: Vec<String>,
+    timer: &mut Timer,
+) -> Result<usize, NamingError> {
+    let words = build_captor(matches)?
+        .capture_words_in_parallel(text, jobs(matches)?)?;
+    timer.lap("capture");
+    let cases = build_filter(matches)?.to_naming_cases_from(words);
+    timer.lap("filter");
+    Ok(cases.len())
+}
+
+/// Answer user's `--invalid-only` option, find captured words
+/// that aren't valid in any format, they are output one per line,
+/// or as a "result" array with `--json` option.
+fn find_invalid_words(
+    matches: &ArgMatches,
+    text: Vec<String>,
+) -> Result<Vec<String>, NamingError> {
+    let mut words = build_captor(matches)?
+        .capture_words_in_parallel(text, jobs(matches)?)?;
+    words.retain(|word| !is_valid_identifier(word));
+    Ok(words)
+}
+
+fn replace_words_in_text(
+    matches: &ArgMatches,
+    target: &str,
+    text: Vec<String>,
+) -> Result<String, NamingError> {
+    let option = |tag: &str| matches.values_of_lossy(tag);
+
+    let captor = Captor::new(option("locator"))?;
+    let filter = build_filter(matches)?;
+    let replacer = Replacer::new(target)?.with_acronyms(option("acronyms"));
+
+    // each file's content is rewritten independently, then concatenated.
+    Ok(text.iter().map(|t| replacer.replace(&captor, &filter, t)).collect())
+}
+
 fn output_as_string(
-    matches: ArgMatches,
+    matches: &ArgMatches,
     convertor: Convertor,
-) -> Result<String, Box<dyn Error>> {
+) -> Result<String, NamingError> {
     let json_flag_is_passed = matches.is_present("json");
     let regex_flag_is_passed = matches.is_present("regex");
+    let pretty_flag_is_passed = matches.is_present("pretty");
 
-    if json_flag_is_passed && regex_flag_is_passed {
-        Ok(convertor.into_regex_json())
+    if matches.is_present("explain") {
+        Ok(convertor.into_explanations())
+    } else if matches.is_present("only-ambiguous") {
+        if json_flag_is_passed {
+            Ok(convertor.into_candidates_json())
+        } else {
+            Ok(convertor.into_candidates())
+        }
+    } else if matches.is_present("count") {
+        if json_flag_is_passed {
+            Ok(convertor.into_counts_json())
+        } else {
+            Ok(convertor.into_counts())
+        }
+    } else if matches.is_present("group-by-case") {
+        if json_flag_is_passed {
+            Ok(convertor.into_groups_json())
+        } else {
+            Ok(convertor.into_groups())
+        }
+    } else if matches.is_present("diff") {
+        // required by `--diff`.
+        let target = matches.value_of("target").unwrap().parse()?;
+        if json_flag_is_passed {
+            Ok(convertor.into_diff_json(target))
+        } else {
+            Ok(convertor.into_diff(target))
+        }
+    } else if matches.is_present("words") {
+        Ok(convertor.into_words())
+    } else if matches.is_present("table") {
+        Ok(convertor.into_table())
+    } else if matches.is_present("yaml") {
+        Ok(convertor.into_yaml())
+    } else if matches.is_present("csv") {
+        Ok(convertor.into_csv(','))
+    } else if matches.is_present("tsv") {
+        Ok(convertor.into_csv('\t'))
+    } else if matches.is_present("ndjson") {
+        Ok(convertor.into_ndjson())
+    } else if json_flag_is_passed && regex_flag_is_passed {
+        if pretty_flag_is_passed {
+            Ok(convertor.into_pretty_regex_json())
+        } else {
+            Ok(convertor.into_regex_json())
+        }
     } else if json_flag_is_passed {
-        Ok(convertor.into_json())
+        if pretty_flag_is_passed {
+            Ok(convertor.into_pretty_json())
+        } else {
+            Ok(convertor.into_json())
+        }
     } else if regex_flag_is_passed {
         Ok(convertor.into_regex())
+    } else if use_color(matches) {
+        Ok(convertor.into_colored_lines())
     } else {
         Ok(convertor.into_lines())
     }
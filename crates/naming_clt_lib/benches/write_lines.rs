@@ -0,0 +1,77 @@
+// Compares `Convertor::into_lines` with `Convertor::write_lines` on a large
+// input, by wall time, the number of heap allocations and the peak heap
+// usage (the whole joined output is kept in memory by `into_lines`).
+//
+// Run with `cargo bench -p naming_clt_lib`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use naming_clt_lib::{to_string_vec, Convertor};
+use naming_lib::which_case;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        PEAK_BYTES.fetch_max(live + layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const WORDS: usize = 100_000;
+
+fn convertor() -> Convertor {
+    let cases = ["pageSize", "user_id", "MAX_VALUE", "http-request"]
+        .iter()
+        .cycle()
+        .take(WORDS)
+        .map(|word| which_case(word))
+        .collect();
+    Convertor::new(Some(to_string_vec(vec!["S", "s", "k", "c", "p"])), cases)
+}
+
+fn measure<F: FnOnce(Convertor)>(name: &str, task: F) {
+    let convertor = convertor();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let live = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(live, Ordering::Relaxed);
+    let start = Instant::now();
+
+    task(convertor);
+
+    println!(
+        "{:<12} {:>9} allocations, {:>6} KiB peak heap, {:>5} ms ({} words)",
+        name,
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        (PEAK_BYTES.load(Ordering::Relaxed) - live) / 1024,
+        start.elapsed().as_millis(),
+        WORDS
+    );
+}
+
+fn main() {
+    measure("into_lines", |convertor| {
+        let mut out = io::sink();
+        out.write_all(convertor.into_lines().as_bytes()).unwrap();
+    });
+    measure("write_lines", |convertor| {
+        convertor.write_lines(&mut io::sink()).unwrap();
+    });
+}
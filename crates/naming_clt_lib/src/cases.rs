@@ -0,0 +1,1819 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use naming_lib::NamingCase;
+
+use super::NamingError;
+
+/// Methods this crate provides on top of naming_lib's `NamingCase`.
+pub trait NamingCaseExt {
+    /// Return the lowercased words that compose the identifier,
+    /// regardless of its original case, e.g. both "snakeCase" and
+    /// "snake_case" yield `["snake", "case"]`.
+    /// An invalid case yields an empty vector.
+    fn words(&self) -> Vec<String>;
+
+    /// Same as `words`, but each word is tagged true if it's an acronym
+    /// in the origin string, i.e. a run of two or more uppercase letters
+    /// in camel or pascal case, e.g. "parseURL" yields
+    /// `[("parse", false), ("url", true)]`.
+    fn tagged_words(&self) -> Vec<(String, bool)>;
+
+    /// Return the snake case name of this case's variant,
+    /// e.g. "camel" for `NamingCase::Camel`.
+    fn case_name(&self) -> &'static str;
+
+    /// Return the kind of this case's variant, e.g. `NamingCaseKind::Camel`
+    /// for `NamingCase::Camel`, so that it can be logged or compared
+    /// without matching on every variant.
+    /// Single word and invalid cases have no kind.
+    ///
+    /// The wrapped string is printed by `NamingCase`'s own `Display`.
+    fn kind(&self) -> Option<NamingCaseKind>;
+
+    /// Convert this case to every `NamingCaseKind` at once,
+    /// keyed (and ordered) as `NamingCaseKind::ALL`.
+    /// Same as `convert`, an invalid case is kept unchanged in all kinds.
+    fn to_all(&self) -> BTreeMap<NamingCaseKind, String>;
+}
+
+impl NamingCaseExt for NamingCase {
+    fn words(&self) -> Vec<String> {
+        canonical_words(self).unwrap_or_default()
+    }
+
+    fn tagged_words(&self) -> Vec<(String, bool)> {
+        match self {
+            // words are lowercased, so acronyms are told
+            // by the uppercase runs in the origin string.
+            NamingCase::Camel(origin) | NamingCase::Pascal(origin) => {
+                split_acronym_words(origin)
+                    .into_iter()
+                    .map(|word| {
+                        let is_acronym = word.len() >= 2
+                            && word.chars().all(|c| {
+                                c.is_ascii_uppercase() || c.is_ascii_digit()
+                            });
+                        (word.to_lowercase(), is_acronym)
+                    })
+                    .collect()
+            }
+            _ => self.words().into_iter().map(|word| (word, false)).collect(),
+        }
+    }
+
+    fn case_name(&self) -> &'static str {
+        match self {
+            NamingCase::SingleWord(_) => "single_word",
+            NamingCase::ScreamingSnake(_) => "screaming_snake",
+            NamingCase::Snake(_) => "snake",
+            NamingCase::Kebab(_) => "kebab",
+            NamingCase::Camel(_) => "camel",
+            NamingCase::Pascal(_) => "pascal",
+            NamingCase::Invalid(_) => "invalid",
+        }
+    }
+
+    fn kind(&self) -> Option<NamingCaseKind> {
+        match self {
+            NamingCase::ScreamingSnake(_) => {
+                Some(NamingCaseKind::ScreamingSnake)
+            }
+            NamingCase::Snake(_) => Some(NamingCaseKind::Snake),
+            NamingCase::Kebab(_) => Some(NamingCaseKind::Kebab),
+            NamingCase::Camel(_) => Some(NamingCaseKind::Camel),
+            NamingCase::Pascal(_) => Some(NamingCaseKind::Pascal),
+            NamingCase::SingleWord(_) | NamingCase::Invalid(_) => None,
+        }
+    }
+
+    fn to_all(&self) -> BTreeMap<NamingCaseKind, String> {
+        let acronyms = HashSet::new();
+        NamingCaseKind::ALL
+            .iter()
+            .map(|kind| {
+                let converted = kind
+                    .convert(self, &acronyms)
+                    .unwrap_or_else(|_| self.to_string());
+                (*kind, converted)
+            })
+            .collect()
+    }
+}
+
+/// Target formats of conversion, each one has a letter used by
+/// `--output` option (e.g. "S" for screaming snake case)
+/// and a name used in structured outputs (e.g. "screaming_snake").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NamingCaseKind {
+    ScreamingSnake,
+    Snake,
+    Kebab,
+    Camel,
+    Pascal,
+    Train,
+    Dot,
+    Flat,
+    UpperFlat,
+    Ada,
+    Cobol,
+    Path,
+}
+
+impl NamingCaseKind {
+    /// All kinds, in the order of their `--output` letters' documentation.
+    pub const ALL: [NamingCaseKind; 12] = [
+        NamingCaseKind::ScreamingSnake,
+        NamingCaseKind::Snake,
+        NamingCaseKind::Kebab,
+        NamingCaseKind::Camel,
+        NamingCaseKind::Pascal,
+        NamingCaseKind::Train,
+        NamingCaseKind::Dot,
+        NamingCaseKind::Flat,
+        NamingCaseKind::UpperFlat,
+        NamingCaseKind::Ada,
+        NamingCaseKind::Cobol,
+        NamingCaseKind::Path,
+    ];
+
+    /// The letter of this kind in `--output` option, e.g. "S".
+    pub fn letter(&self) -> &'static str {
+        match self {
+            NamingCaseKind::ScreamingSnake => "S",
+            NamingCaseKind::Snake => "s",
+            NamingCaseKind::Kebab => "k",
+            NamingCaseKind::Camel => "c",
+            NamingCaseKind::Pascal => "p",
+            NamingCaseKind::Train => "t",
+            NamingCaseKind::Dot => "d",
+            NamingCaseKind::Flat => "f",
+            NamingCaseKind::UpperFlat => "F",
+            NamingCaseKind::Ada => "a",
+            NamingCaseKind::Cobol => "C",
+            NamingCaseKind::Path => "/",
+        }
+    }
+
+    /// The name of this kind, used as keys or headers
+    /// in structured outputs, e.g. "screaming_snake".
+    pub fn name(&self) -> &'static str {
+        match self {
+            NamingCaseKind::ScreamingSnake => "screaming_snake",
+            NamingCaseKind::Snake => "snake",
+            NamingCaseKind::Kebab => "kebab",
+            NamingCaseKind::Camel => "camel",
+            NamingCaseKind::Pascal => "pascal",
+            NamingCaseKind::Train => "train",
+            NamingCaseKind::Dot => "dot",
+            NamingCaseKind::Flat => "flat",
+            NamingCaseKind::UpperFlat => "upper_flat",
+            NamingCaseKind::Ada => "ada",
+            NamingCaseKind::Cobol => "cobol",
+            NamingCaseKind::Path => "path",
+        }
+    }
+
+    /// Convert given case to this format, words in the given (uppercase)
+    /// acronym set are fully uppercased in formats that capitalize words
+    /// (camel, pascal, train and ada case).
+    pub fn convert(
+        &self,
+        case: &NamingCase,
+        acronyms: &HashSet<String>,
+    ) -> Result<String, &'static str> {
+        match self {
+            NamingCaseKind::ScreamingSnake => to_screaming_snake(case),
+            NamingCaseKind::Snake => to_snake(case),
+            NamingCaseKind::Kebab => to_kebab(case),
+            NamingCaseKind::Camel => to_camel_with_acronyms(case, acronyms),
+            NamingCaseKind::Pascal => to_pascal_with_acronyms(case, acronyms),
+            NamingCaseKind::Train => to_train_with_acronyms(case, acronyms),
+            NamingCaseKind::Dot => to_dot(case),
+            NamingCaseKind::Flat => to_flat(case),
+            NamingCaseKind::UpperFlat => to_upper_flat(case),
+            NamingCaseKind::Ada => to_ada_with_acronyms(case, acronyms),
+            NamingCaseKind::Cobol => to_cobol(case),
+            NamingCaseKind::Path => to_path(case),
+        }
+    }
+}
+
+impl fmt::Display for NamingCaseKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for NamingCaseKind {
+    type Err = NamingError;
+
+    /// Accept both the letter (e.g. "S") and the name, words of which
+    /// may also be joined by "-" (e.g. "screaming_snake" or "screaming-snake").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.replace('-', "_");
+        NamingCaseKind::ALL
+            .iter()
+            .find(|kind| kind.letter() == s || kind.name() == name)
+            .copied()
+            .ok_or_else(|| NamingError::UnknownCase(s.to_string()))
+    }
+}
+
+/// Convert one identifier to the target format in one call,
+/// e.g. `convert("pageSize", NamingCaseKind::Snake)` -> "page_size".
+///
+/// This is the recommended entry point for embedders that just want to
+/// turn one string into another case, without the `Captor` -> `Filter`
+/// -> `Convertor` pipeline used by the command line tool.
+/// Invalid identifiers (e.g. "-invalid_") are returned unchanged.
+pub fn convert(input: &str, target: NamingCaseKind) -> String {
+    target
+        .convert(&which_case(input), &HashSet::new())
+        .unwrap_or_else(|_| input.to_string())
+}
+
+/// Same as `naming_lib::which_case`, but letters are told apart by
+/// `char::is_uppercase` and `char::is_lowercase` instead of ASCII ranges,
+/// so that words like "straße_name" or "nomRéseau" are recognized.
+/// All classification in this crate goes through it.
+pub fn which_case(word: &str) -> NamingCase {
+    let s = word.to_string();
+    if is_single_word(word) {
+        NamingCase::SingleWord(s)
+    } else if is_screaming_snake(word) {
+        NamingCase::ScreamingSnake(s)
+    } else if is_snake(word) {
+        NamingCase::Snake(s)
+    } else if is_kebab(word) {
+        NamingCase::Kebab(s)
+    } else if is_camel(word) {
+        NamingCase::Camel(s)
+    } else if is_pascal(word) {
+        NamingCase::Pascal(s)
+    } else {
+        NamingCase::Invalid(s)
+    }
+}
+
+// the Unicode-aware `[a-z\d]` and `[A-Z\d]` of naming_lib's patterns.
+fn is_lower_or_digit(c: char) -> bool {
+    c.is_lowercase() || c.is_numeric()
+}
+
+fn is_upper_or_digit(c: char) -> bool {
+    c.is_uppercase() || c.is_numeric()
+}
+
+// non-empty parts of given characters joined by single separators.
+fn is_joined_by(
+    word: &str,
+    separator: char,
+    is_char: fn(char) -> bool,
+) -> bool {
+    word.split(separator)
+        .all(|part| !part.is_empty() && part.chars().all(is_char))
+}
+
+/// One word without separators: all lowercase ("page"),
+/// all uppercase ("PAGE") or capitalized ("Page").
+pub fn is_single_word(word: &str) -> bool {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) if first.is_uppercase() => {
+            chars.clone().all(is_lower_or_digit) || chars.all(is_upper_or_digit)
+        }
+        Some(first) if is_lower_or_digit(first) => {
+            let rest = chars.as_str();
+            rest.chars().all(is_lower_or_digit)
+                || (first.is_numeric() && rest.chars().all(is_upper_or_digit))
+        }
+        _ => false,
+    }
+}
+
+/// Uppercase words joined by "_", e.g. "PAGE_SIZE".
+pub fn is_screaming_snake(word: &str) -> bool {
+    is_joined_by(word, '_', is_upper_or_digit)
+}
+
+/// Lowercase words joined by "_", e.g. "page_size".
+pub fn is_snake(word: &str) -> bool {
+    is_joined_by(word, '_', is_lower_or_digit)
+}
+
+/// Lowercase words joined by "-", e.g. "page-size".
+pub fn is_kebab(word: &str) -> bool {
+    is_joined_by(word, '-', is_lower_or_digit)
+}
+
+/// Letters and digits starting with a lowercase letter or a digit,
+/// e.g. "pageSize" (or a single lowercase word "page").
+pub fn is_camel(word: &str) -> bool {
+    word.starts_with(is_lower_or_digit)
+        && word.chars().all(|c| c.is_uppercase() || is_lower_or_digit(c))
+}
+
+/// Letters and digits starting with an uppercase letter, e.g. "PageSize".
+pub fn is_pascal(word: &str) -> bool {
+    word.starts_with(char::is_uppercase)
+        && word.chars().all(|c| c.is_uppercase() || is_lower_or_digit(c))
+}
+
+/// Return every naming case whose predicate accepts given word,
+/// the first element is always same as `which_case`'s result,
+/// the rest are ordered from the most specific format to the least.
+/// Return an empty vector if the word isn't valid in any format.
+///
+/// Useful for inputs like "abc" or "ABC" which are genuinely ambiguous.
+pub fn which_cases(word: &str) -> Vec<NamingCase> {
+    let mut cases: Vec<NamingCase> = CANDIDATES
+        .iter()
+        .filter(|candidate| (candidate.predicate)(word))
+        .map(|candidate| (candidate.variant)(word.to_string()))
+        .collect();
+    if cases.is_empty() {
+        return cases;
+    }
+
+    let first = which_case(word);
+    cases.retain(|case| *case != first);
+    cases.insert(0, first);
+    cases
+}
+
+/// Return true if given word is valid in any of the recognized formats,
+/// e.g. false for "-invalid_" or "a b".
+pub fn is_valid_identifier(word: &str) -> bool {
+    CANDIDATES.iter().any(|candidate| (candidate.predicate)(word))
+}
+
+/// How an identifier mixes naming cases, see `case_conflict`.
+#[derive(Debug, PartialEq)]
+pub struct CaseConflict {
+    /// Distinct separators ('_' or '-') in order of appearance.
+    pub separators: Vec<char>,
+    /// Separated segments that have camel humps, e.g. "barBaz".
+    pub humped_segments: Vec<String>,
+    /// Lowercase words split on both separators and camel humps,
+    /// e.g. "foo", "bar", "baz" of "foo_barBaz".
+    pub words: Vec<String>,
+}
+
+impl fmt::Display for CaseConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quote = |s: &str| format!("`{}`", s);
+        let mut reasons = Vec::new();
+        if self.separators.len() > 1 {
+            let separators: Vec<String> = self
+                .separators
+                .iter()
+                .map(|separator| quote(&separator.to_string()))
+                .collect();
+            reasons.push(format!(
+                "separators {} are mixed",
+                separators.join(", ")
+            ));
+        }
+        if !self.humped_segments.is_empty() {
+            let segments: Vec<String> =
+                self.humped_segments.iter().map(|s| quote(s)).collect();
+            reasons.push(format!(
+                "separated segments with camel humps: {}",
+                segments.join(", ")
+            ));
+        }
+        write!(f, "{}", reasons.join("; "))
+    }
+}
+
+/// Return how given identifier mixes naming cases, `None` if it doesn't,
+/// e.g. "foo_barBaz" is snake case with a camel case segment,
+/// "foo_bar-baz" uses both snake and kebab separators.
+///
+/// naming_lib rejects such hybrids as invalid words,
+/// they are often left by a half done rename.
+pub fn case_conflict(word: &str) -> Option<CaseConflict> {
+    let mut separators = Vec::new();
+    for c in word.chars().filter(|c| *c == '_' || *c == '-') {
+        if !separators.contains(&c) {
+            separators.push(c);
+        }
+    }
+    if separators.is_empty() {
+        return None;
+    }
+
+    let mut humped_segments = Vec::new();
+    let mut words = Vec::new();
+    for segment in word.split(['_', '-']) {
+        let segment_words = split_camel_humps(segment);
+        if segment_words.len() > 1 {
+            humped_segments.push(segment.to_string());
+        }
+        words.extend(segment_words);
+    }
+
+    if separators.len() > 1 || !humped_segments.is_empty() {
+        Some(CaseConflict { separators, humped_segments, words })
+    } else {
+        None
+    }
+}
+
+/// Return false if given identifier mixes naming cases,
+/// see `case_conflict`.
+pub fn is_consistent(word: &str) -> bool {
+    case_conflict(word).is_none()
+}
+
+/// "barBaz" -> ["bar", "baz"], "HTTPResponse" -> ["http", "response"].
+fn split_camel_humps(segment: &str) -> Vec<String> {
+    split_acronym_words(segment)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Return true if given word is a lone acronym, i.e. two or more
+/// uppercase letters (digits allowed after the first letter)
+/// without separators, e.g. "ID", "URL", "IO" or "MD5".
+///
+/// `which_case` classifies such words like any other uppercase word,
+/// camel and pascal conversions in this crate keep them verbatim,
+/// as "Id" or "id" would lose the acronym.
+pub fn is_acronym(word: &str) -> bool {
+    word.chars().count() >= 2
+        && word.starts_with(char::is_uppercase)
+        && word.chars().all(is_upper_or_digit)
+}
+
+/// Return true if given word is strictly in camelCase,
+/// which is what the camel case filter ("c") accepts:
+///
+/// * it starts with a lowercase letter: "pageSize", not "PageSize";
+/// * it has an uppercase letter after the first one: "pageSize",
+///   not a single word like "page";
+/// * it has only letters and digits, no separators:
+///   "md5Sum", not "page_Size";
+/// * it's recognized as camel case, see `is_camel_lenient`.
+pub fn is_camel_strict(word: &str) -> bool {
+    word.starts_with(char::is_lowercase)
+        && word.chars().any(char::is_uppercase)
+        && is_camel(word)
+}
+
+/// Return true if `is_camel` recognizes given word as camel case,
+/// which also accepts a single lowercase word like "page".
+///
+/// This is what the hungarian notation filter ("h") accepts, and what
+/// decides whether a word is read as hungarian notation
+/// (see `from_hungarian_notation_with_prefixes`), e.g. "strName".
+pub fn is_camel_lenient(word: &str) -> bool {
+    is_camel(word)
+}
+
+/// Return true if given word is already in given format,
+/// e.g. `is_case("page_size", NamingCaseKind::Snake)` is true.
+///
+/// Formats recognized by `which_case` use its `is_*` predicates,
+/// the others are checked by their own shapes
+/// (e.g. Train-Case: capitalized words joined with hyphens).
+/// Same as the predicates, a single lowercase word like "page"
+/// is valid in several formats.
+pub fn is_case(word: &str, kind: NamingCaseKind) -> bool {
+    let all_words = |separator: char, is_word: fn(&str) -> bool| {
+        word.split(separator).all(is_word)
+    };
+    match kind {
+        NamingCaseKind::ScreamingSnake => is_screaming_snake(word),
+        NamingCaseKind::Snake => is_snake(word),
+        NamingCaseKind::Kebab => is_kebab(word),
+        NamingCaseKind::Camel => is_camel(word),
+        NamingCaseKind::Pascal => is_pascal(word),
+        NamingCaseKind::Train => all_words('-', is_capitalized_word),
+        NamingCaseKind::Dot => all_words('.', is_lowercase_word),
+        NamingCaseKind::Flat => is_lowercase_word(word),
+        NamingCaseKind::UpperFlat => is_uppercase_word(word),
+        NamingCaseKind::Ada => is_ada(word),
+        NamingCaseKind::Cobol => is_cobol(word),
+        NamingCaseKind::Path => is_path(word),
+    }
+}
+
+/// Return true if given word is in Ada_Case, i.e. capitalized words
+/// joined with single underscores, e.g. "Snake_Case".
+/// All-lowercase snake case and all-caps screaming snake case
+/// words are rejected.
+pub fn is_ada(word: &str) -> bool {
+    word.split('_').all(is_capitalized_word)
+}
+
+/// Return true if given word is in COBOL-CASE, i.e. uppercase words
+/// joined with single hyphens, e.g. "CUSTOMER-ID".
+/// Never overlaps with kebab case, whose words are lowercase.
+pub fn is_cobol(word: &str) -> bool {
+    word.split('-').all(is_uppercase_word)
+}
+
+/// Return true if given word is in path/case, i.e. lowercase words
+/// joined with single slashes, e.g. "user/profile/page".
+pub fn is_path(word: &str) -> bool {
+    word.split('/').all(is_lowercase_word)
+}
+
+// A letter followed by letters or digits,
+// the case of letters is checked by given predicate.
+fn is_word_with(
+    word: &str,
+    first: fn(char) -> bool,
+    rest: fn(char) -> bool,
+) -> bool {
+    let mut chars = word.chars();
+    matches!(chars.next(), Some(c) if first(c))
+        && chars.all(|c| rest(c) || c.is_numeric())
+}
+
+fn is_lowercase_word(word: &str) -> bool {
+    is_word_with(word, char::is_lowercase, char::is_lowercase)
+}
+
+fn is_uppercase_word(word: &str) -> bool {
+    is_word_with(word, char::is_uppercase, char::is_uppercase)
+}
+
+fn is_capitalized_word(word: &str) -> bool {
+    is_word_with(word, char::is_uppercase, char::is_lowercase)
+}
+
+// Intermediate type for finding all
+// naming cases that a word matches.
+struct Candidate {
+    pub predicate: fn(&str) -> bool,
+    pub variant: fn(String) -> NamingCase,
+    // why a word that the predicate rejects isn't in this case.
+    pub rejection: fn(&str) -> &'static str,
+}
+
+const CANDIDATES: [Candidate; 5] = [
+    Candidate {
+        predicate: is_screaming_snake,
+        variant: NamingCase::ScreamingSnake,
+        rejection: |word| match word {
+            _ if has_lowercase(word) => "has lowercase letters",
+            _ if has_separator_except(word, '_') => {
+                "has separators other than `_`"
+            }
+            _ => "isn't uppercase words joined by `_`",
+        },
+    },
+    Candidate {
+        predicate: is_snake,
+        variant: NamingCase::Snake,
+        rejection: |word| match word {
+            _ if has_uppercase(word) => "has uppercase letters",
+            _ if has_separator_except(word, '_') => {
+                "has separators other than `_`"
+            }
+            _ => "isn't lowercase words joined by `_`",
+        },
+    },
+    Candidate {
+        predicate: is_kebab,
+        variant: NamingCase::Kebab,
+        rejection: |word| match word {
+            _ if has_uppercase(word) => "has uppercase letters",
+            _ if has_separator_except(word, '-') => {
+                "has separators other than `-`"
+            }
+            _ => "isn't lowercase words joined by `-`",
+        },
+    },
+    Candidate {
+        predicate: is_camel,
+        variant: NamingCase::Camel,
+        rejection: |word| match word {
+            _ if word.starts_with(|c: char| c.is_uppercase()) => {
+                "starts with an uppercase letter"
+            }
+            _ if has_separator(word) => "has separators",
+            _ => "isn't a lowercase word followed by capitalized words",
+        },
+    },
+    Candidate {
+        predicate: is_pascal,
+        variant: NamingCase::Pascal,
+        rejection: |word| match word {
+            _ if !word.starts_with(|c: char| c.is_uppercase()) => {
+                "doesn't start with an uppercase letter"
+            }
+            _ if has_separator(word) => "has separators",
+            _ => "isn't capitalized words without separators",
+        },
+    },
+];
+
+fn has_lowercase(word: &str) -> bool {
+    word.chars().any(char::is_lowercase)
+}
+
+fn has_uppercase(word: &str) -> bool {
+    word.chars().any(char::is_uppercase)
+}
+
+fn has_separator(word: &str) -> bool {
+    word.chars().any(|c| !c.is_alphanumeric())
+}
+
+fn has_separator_except(word: &str, allowed: char) -> bool {
+    word.chars().any(|c| !c.is_alphanumeric() && c != allowed)
+}
+
+/// Why a word got its classification, see `explain`.
+#[derive(Debug, PartialEq)]
+pub struct Explanation {
+    /// Same as `which_case`'s result.
+    pub case: NamingCase,
+    /// The words the case is split into, see `NamingCaseExt::words`.
+    pub words: Vec<String>,
+    /// Other cases the word is also valid in, see `which_cases`.
+    pub alternatives: Vec<&'static str>,
+    /// Cases the word isn't valid in, with the reasons.
+    pub rejected: Vec<(&'static str, &'static str)>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_none = |items: Vec<&str>| {
+            if items.is_empty() {
+                "-".to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+        writeln!(f, "{}", self.case)?;
+        writeln!(f, "  case: {}", self.case.case_name())?;
+        writeln!(
+            f,
+            "  words: {}",
+            or_none(self.words.iter().map(String::as_str).collect())
+        )?;
+        write!(f, "  also valid: {}", or_none(self.alternatives.clone()))?;
+        for (name, reason) in &self.rejected {
+            write!(f, "\n  not {}: {}", name, reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// Explain how given word is classified: the case `which_case`
+/// picks, the words it's split into, the other cases it's also valid in,
+/// and why it isn't in the rest (screaming snake, snake, kebab, camel
+/// and pascal case), e.g. "pageSize" isn't in snake case
+/// because it "has uppercase letters".
+pub fn explain(word: &str) -> Explanation {
+    let case = which_case(word);
+    let alternatives =
+        which_cases(word).iter().skip(1).map(|case| case.case_name()).collect();
+    let rejected = CANDIDATES
+        .iter()
+        .filter(|candidate| !(candidate.predicate)(word))
+        .map(|candidate| {
+            let name = (candidate.variant)(String::new()).case_name();
+            (name, (candidate.rejection)(word))
+        })
+        .collect();
+    Explanation { words: case.words(), case, alternatives, rejected }
+}
+
+/// The lowercased words of given case, which all formatters
+/// in this crate are built from, so that converting a word to any
+/// format and back yields the same words.
+///
+/// Separators only delimit words: leading, trailing and repeated ones
+/// never yield empty words, e.g. "__init__" -> ["init"],
+/// "a__b" -> ["a", "b"].
+///
+/// Digits belong to the word before them, as they do in camel case
+/// (see `split_acronym_words`), a word of only digits is attached to
+/// the previous one, e.g. "VERSION_2_CASE" -> ["version2", "case"],
+/// same as "version2Case".
+fn canonical_words(case: &NamingCase) -> Result<Vec<String>, &'static str> {
+    let words = match case {
+        NamingCase::Invalid(_) => return Err("can't convert an invalid case"),
+        NamingCase::SingleWord(word) => vec![word.as_str()],
+        NamingCase::ScreamingSnake(word) | NamingCase::Snake(word) => {
+            word.split('_').collect()
+        }
+        NamingCase::Kebab(word) => word.split('-').collect(),
+        NamingCase::Camel(word) | NamingCase::Pascal(word) => {
+            acronym_word_ranges(word)
+                .into_iter()
+                .map(|range| &word[range])
+                .collect()
+        }
+    };
+    let mut canonical: Vec<String> = Vec::new();
+    for word in words.into_iter().filter(|word| !word.is_empty()) {
+        match canonical.last_mut() {
+            Some(last) if word.chars().all(char::is_numeric) => {
+                last.push_str(word)
+            }
+            _ => canonical.push(word.to_lowercase()),
+        }
+    }
+    Ok(canonical)
+}
+
+/// How each word of a `CaseSpec` is cased.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordCase {
+    /// e.g. "word".
+    Lower,
+    /// e.g. "WORD".
+    Upper,
+    /// e.g. "Word", acronyms (see `Convertor::with_acronyms`) are
+    /// fully uppercased.
+    Title,
+}
+
+impl WordCase {
+    fn apply(self, word: &str, acronyms: &HashSet<String>) -> String {
+        match self {
+            WordCase::Lower => word.to_string(),
+            WordCase::Upper => word.to_uppercase(),
+            WordCase::Title => capitalize_or_acronym(word, acronyms),
+        }
+    }
+}
+
+/// A generic naming case: words cased by `word_case` and joined by
+/// `separator`, except that the first word is cased by `first_word_case`,
+/// e.g. SCREAMING-TRAIN-CASE is `CaseSpec::new("-", WordCase::Upper)`.
+///
+/// The built-in formats are presets of it, see `CaseSpec::preset`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseSpec {
+    pub separator: String,
+    pub word_case: WordCase,
+    pub first_word_case: WordCase,
+}
+
+impl CaseSpec {
+    pub fn new(separator: &str, word_case: WordCase) -> CaseSpec {
+        CaseSpec {
+            separator: separator.to_string(),
+            word_case,
+            first_word_case: word_case,
+        }
+    }
+
+    pub fn with_first_word_case(mut self, first_word_case: WordCase) -> Self {
+        self.first_word_case = first_word_case;
+        self
+    }
+
+    /// The spec of a built-in format.
+    ///
+    pub fn preset(kind: NamingCaseKind) -> CaseSpec {
+        use WordCase::*;
+        match kind {
+            NamingCaseKind::ScreamingSnake => CaseSpec::new("_", Upper),
+            NamingCaseKind::Snake => CaseSpec::new("_", Lower),
+            NamingCaseKind::Kebab => CaseSpec::new("-", Lower),
+            NamingCaseKind::Camel => {
+                CaseSpec::new("", Title).with_first_word_case(Lower)
+            }
+            NamingCaseKind::Pascal => CaseSpec::new("", Title),
+            NamingCaseKind::Train => CaseSpec::new("-", Title),
+            NamingCaseKind::Dot => CaseSpec::new(".", Lower),
+            NamingCaseKind::Flat => CaseSpec::new("", Lower),
+            NamingCaseKind::UpperFlat => CaseSpec::new("", Upper),
+            NamingCaseKind::Ada => CaseSpec::new("_", Title),
+            NamingCaseKind::Cobol => CaseSpec::new("-", Upper),
+            NamingCaseKind::Path => CaseSpec::new("/", Lower),
+        }
+    }
+
+    /// Whether any word is capitalized, such formats follow
+    /// the `--acronym-style` option.
+    pub fn capitalizes_words(&self) -> bool {
+        self.word_case == WordCase::Title
+            || self.first_word_case == WordCase::Title
+    }
+}
+
+/// Parse a "custom:<separator>:<word case>[:<first word case>]" value
+/// of `--output` option, word cases are "lower", "upper" or "title",
+/// e.g. "custom:-:upper" is COBOL-CASE, "custom::title:lower" is camelCase.
+impl FromStr for CaseSpec {
+    type Err = NamingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let word_case = |name: &str| match name {
+            "lower" => Some(WordCase::Lower),
+            "upper" => Some(WordCase::Upper),
+            "title" => Some(WordCase::Title),
+            _ => None,
+        };
+        let parts: Vec<&str> = match s.strip_prefix("custom:") {
+            Some(spec) => spec.split(':').collect(),
+            None => Vec::new(),
+        };
+        let spec = match parts.as_slice() {
+            [separator, case] => {
+                word_case(case).map(|case| CaseSpec::new(separator, case))
+            }
+            [separator, case, first] => {
+                word_case(case).zip(word_case(first)).map(|(case, first)| {
+                    CaseSpec::new(separator, case).with_first_word_case(first)
+                })
+            }
+            _ => None,
+        };
+        spec.ok_or_else(|| NamingError::InvalidOptionValue {
+            option: "output".to_string(),
+            value: s.to_string(),
+            expected: "custom:<separator>:<word case>[:<first word case>] \
+            (word case: lower, upper or title)"
+                .to_string(),
+        })
+    }
+}
+
+/// Convert given case to the format described by given spec,
+/// e.g. "page_size" with `CaseSpec::new("-", WordCase::Upper)`
+/// -> "PAGE-SIZE".
+pub fn to_custom(
+    case: &NamingCase,
+    spec: &CaseSpec,
+    acronyms: &HashSet<String>,
+) -> Result<String, &'static str> {
+    Ok(canonical_words(case)?
+        .iter()
+        .enumerate()
+        .map(|(index, word)| match index {
+            0 => spec.first_word_case.apply(word, acronyms),
+            _ => spec.word_case.apply(word, acronyms),
+        })
+        .collect::<Vec<String>>()
+        .join(&spec.separator))
+}
+
+fn to_preset(
+    case: &NamingCase,
+    kind: NamingCaseKind,
+    acronyms: &HashSet<String>,
+) -> Result<String, &'static str> {
+    to_custom(case, &CaseSpec::preset(kind), acronyms)
+}
+
+/// Convert given case to SCREAMING_SNAKE_CASE (e.g. "PAGE_SIZE").
+///
+/// Same as `NamingCase::to_screaming_snake`, but built from the words
+/// of this crate (see `which_case`), as all formats here are.
+pub fn to_screaming_snake(case: &NamingCase) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::ScreamingSnake, &HashSet::new())
+}
+
+/// Convert given case to snake_case (e.g. "page_size"),
+/// see `to_screaming_snake`.
+pub fn to_snake(case: &NamingCase) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::Snake, &HashSet::new())
+}
+
+/// Convert given case to kebab-case (e.g. "page-size"),
+/// see `to_screaming_snake`.
+pub fn to_kebab(case: &NamingCase) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::Kebab, &HashSet::new())
+}
+
+/// Convert given case to Train-Case (e.g. "Content-Type"),
+/// which is kebab case with each word capitalized.
+pub fn to_train(case: &NamingCase) -> Result<String, &'static str> {
+    to_train_with_acronyms(case, &HashSet::new())
+}
+
+/// Same as `to_train`, words that appear in the given (uppercase)
+/// acronym set are emitted fully uppercased, e.g. "Content-ID".
+pub fn to_train_with_acronyms(
+    case: &NamingCase,
+    acronyms: &HashSet<String>,
+) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::Train, acronyms)
+}
+
+/// Convert given case to dot.case (e.g. "user.first.name"),
+/// which is snake case with dots as separators.
+pub fn to_dot(case: &NamingCase) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::Dot, &HashSet::new())
+}
+
+/// Convert given case to flatcase (e.g. "flatcase"),
+/// all words lowercased and joined without separators.
+///
+/// Note that this conversion is lossy: word boundaries
+/// can't be recovered from a flatcase string.
+pub fn to_flat(case: &NamingCase) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::Flat, &HashSet::new())
+}
+
+/// Convert given case to UPPERFLATCASE (e.g. "UPPERFLAT"),
+/// all words uppercased and joined without separators.
+pub fn to_upper_flat(case: &NamingCase) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::UpperFlat, &HashSet::new())
+}
+
+/// Convert given case to Ada_Case (e.g. "Snake_Case"),
+/// which is snake case with each word capitalized.
+pub fn to_ada(case: &NamingCase) -> Result<String, &'static str> {
+    to_ada_with_acronyms(case, &HashSet::new())
+}
+
+/// Same as `to_ada`, words that appear in the given (uppercase)
+/// acronym set are emitted fully uppercased, e.g. "Customer_ID".
+pub fn to_ada_with_acronyms(
+    case: &NamingCase,
+    acronyms: &HashSet<String>,
+) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::Ada, acronyms)
+}
+
+/// Convert given case to COBOL-CASE (e.g. "CUSTOMER-ID"),
+/// which is kebab case with all words uppercased.
+pub fn to_cobol(case: &NamingCase) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::Cobol, &HashSet::new())
+}
+
+/// Convert given case to path/case (e.g. "user/profile/page"),
+/// which is snake case with slashes as separators.
+pub fn to_path(case: &NamingCase) -> Result<String, &'static str> {
+    to_preset(case, NamingCaseKind::Path, &HashSet::new())
+}
+
+/// The opposite of `to_path`, recognize a path/case word
+/// as a snake case one, e.g. "user/profile" -> `Snake("user_profile")`,
+/// so that it can be converted to the other formats.
+/// Return None if the word isn't in path/case.
+pub fn from_path(word: &str) -> Option<NamingCase> {
+    if is_path(word) {
+        Some(which_case(&word.replace('/', "_")))
+    } else {
+        None
+    }
+}
+
+/// Separators between segments of a qualified name,
+/// "::" goes first so that it isn't taken as a ":" segment.
+const QUALIFIERS: [&str; 2] = ["::", "."];
+
+/// Split a qualified name (e.g. "obj.method", "ns::Type") into its
+/// segments and the separators between them,
+/// e.g. "ns::Type.value" -> (["ns", "Type", "value"], ["::", "."]).
+/// Return None for a plain identifier, or if a segment is empty
+/// (e.g. "a..b", ".a").
+pub fn split_qualified(word: &str) -> Option<(Vec<&str>, Vec<&str>)> {
+    let mut segments = Vec::new();
+    let mut separators = Vec::new();
+    let mut rest = word;
+    loop {
+        let next = QUALIFIERS
+            .iter()
+            .filter_map(|separator| Some((rest.find(separator)?, *separator)))
+            .min_by_key(|(index, _)| *index);
+        let (index, separator) = match next {
+            Some(next) => next,
+            None => break,
+        };
+        segments.push(&rest[..index]);
+        separators.push(separator);
+        rest = &rest[index + separator.len()..];
+    }
+    segments.push(rest);
+    if separators.is_empty() || segments.iter().any(|s| s.is_empty()) {
+        return None;
+    }
+    Some((segments, separators))
+}
+
+/// Convert given case to camelCase, words that appear in
+/// the given (uppercase) acronym set are emitted fully uppercased,
+/// e.g. "parse_url_id" with {"URL", "ID"} -> "parseURLID".
+///
+/// The first word is always kept in lowercase,
+/// except for a lone acronym (see `is_acronym`), which is kept as is.
+pub fn to_camel_with_acronyms(
+    case: &NamingCase,
+    acronyms: &HashSet<String>,
+) -> Result<String, &'static str> {
+    if is_acronym(&case.to_string()) {
+        return Ok(case.to_string());
+    }
+    to_preset(case, NamingCaseKind::Camel, acronyms)
+}
+
+/// Convert given case to PascalCase, words that appear in
+/// the given (uppercase) acronym set are emitted fully uppercased,
+/// e.g. "parse_url_id" with {"URL", "ID"} -> "ParseURLID".
+/// A lone acronym (see `is_acronym`) is kept as is.
+pub fn to_pascal_with_acronyms(
+    case: &NamingCase,
+    acronyms: &HashSet<String>,
+) -> Result<String, &'static str> {
+    if is_acronym(&case.to_string()) {
+        return Ok(case.to_string());
+    }
+    to_preset(case, NamingCaseKind::Pascal, acronyms)
+}
+
+/// Same as `naming_lib::from_hungarian_notation`, but if the leading run of
+/// lowercase letters (before the first uppercase letter) is one of the given
+/// type prefixes, strip the whole run,
+/// e.g. "lpszUserName" with {"lpsz"} -> `NamingCase::Pascal("UserName")`.
+///
+/// Words that don't start with a known prefix fall back to
+/// `naming_lib::from_hungarian_notation`.
+pub fn from_hungarian_notation_with_prefixes(
+    word: &str,
+    prefixes: &HashSet<String>,
+) -> NamingCase {
+    match word.find(|c: char| c.is_uppercase()) {
+        Some(index) if prefixes.contains(&word[..index]) => {
+            which_case(&word[index..])
+        }
+        _ => from_hungarian_notation(word),
+    }
+}
+
+/// Same as `naming_lib::from_hungarian_notation`, strip the leading run
+/// of lowercase letters (the type prefix), e.g. "strName" -> `Pascal("Name")`,
+/// but classified by `which_case`.
+pub fn from_hungarian_notation(word: &str) -> NamingCase {
+    match word.find(char::is_uppercase) {
+        Some(index) => which_case(&word[index..]),
+        None => which_case(word),
+    }
+}
+
+/// Answer user's `--acronym-style` option, how acronyms in camel or
+/// pascal case inputs (see `NamingCaseExt::tagged_words`) are rendered
+/// by formats that capitalize words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AcronymStyle {
+    /// Fully uppercased, e.g. "parseURL".
+    Upper,
+    /// Capitalized same as other words, e.g. "parseUrl".
+    #[default]
+    Title,
+    /// Lowercased and glued to the previous word, e.g. "parseurl".
+    Lower,
+}
+
+impl FromStr for AcronymStyle {
+    type Err = NamingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "upper" => Ok(AcronymStyle::Upper),
+            "title" => Ok(AcronymStyle::Title),
+            "lower" => Ok(AcronymStyle::Lower),
+            _ => Err(NamingError::InvalidOptionValue {
+                option: "acronym-style".to_string(),
+                value: s.to_string(),
+                expected: "one of upper, title and lower".to_string(),
+            }),
+        }
+    }
+}
+
+/// Prepare the case and the acronym set for a conversion in given style:
+/// the case is rebuilt from its tagged words. `Upper` adds the case's
+/// acronyms to the set, `Lower` glues them to their previous words,
+/// an acronym at the start stays a word.
+pub(crate) fn with_acronym_style(
+    case: &NamingCase,
+    acronyms: &HashSet<String>,
+    style: AcronymStyle,
+) -> (NamingCase, HashSet<String>) {
+    let words = case.tagged_words();
+    let mut acronyms = acronyms.clone();
+    if !words.iter().any(|(_, is_acronym)| *is_acronym) {
+        return (which_case(&case.to_string()), acronyms);
+    }
+    let mut joined: Vec<String> = Vec::new();
+    for (word, is_acronym) in words {
+        match style {
+            AcronymStyle::Upper if is_acronym => {
+                acronyms.insert(word.to_uppercase());
+                joined.push(word);
+            }
+            AcronymStyle::Lower if is_acronym && !joined.is_empty() => {
+                joined.last_mut().unwrap().push_str(&word)
+            }
+            _ => joined.push(word),
+        }
+    }
+    (which_case(&joined.join("_")), acronyms)
+}
+
+/// Split a camel or pascal case word on the standard word boundaries:
+/// before an uppercase letter following a lowercase letter or a digit,
+/// and before the last letter of an uppercase run followed by a lowercase
+/// letter, so that an acronym stays one word, and digits stay with
+/// the word before them, e.g.
+/// "HTTPServer" -> ["HTTP", "Server"],
+/// "getHTTPSPage" -> ["get", "HTTPS", "Page"],
+/// "getHTTP2Response" -> ["get", "HTTP2", "Response"],
+/// "parse2Json" -> ["parse2", "Json"].
+pub fn split_acronym_words(word: &str) -> Vec<String> {
+    acronym_word_ranges(word)
+        .into_iter()
+        .map(|range| word[range].to_string())
+        .collect()
+}
+
+/// The ranges of the words `split_acronym_words` returns.
+pub(crate) fn acronym_word_ranges(word: &str) -> Vec<Range<usize>> {
+    let chars: Vec<(usize, char)> = word.char_indices().collect();
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for i in 1..chars.len() {
+        let (offset, current) = chars[i];
+        let previous = chars[i - 1].1;
+        let next_is_lowercase =
+            matches!(chars.get(i + 1), Some((_, c)) if c.is_lowercase());
+        let boundary = current.is_uppercase()
+            && (previous.is_lowercase()
+                || previous.is_numeric()
+                || (previous.is_uppercase() && next_is_lowercase));
+        if boundary {
+            ranges.push(start..offset);
+            start = offset;
+        }
+    }
+    if start < word.len() {
+        ranges.push(start..word.len());
+    }
+    ranges
+}
+
+/// Rebuild a camel or pascal case as a snake case one from its words,
+/// e.g. "HTTPSConnection" -> `Snake("https_connection")`.
+/// Other cases are returned as they are.
+///
+/// Words of every case are split by `split_acronym_words` already,
+/// so no converted string changes, it's kept for `--merge-acronyms`.
+pub fn merge_acronyms(case: &NamingCase) -> NamingCase {
+    match case {
+        NamingCase::Camel(_) | NamingCase::Pascal(_) => {
+            which_case(&case.words().join("_"))
+        }
+        _ => which_case(&case.to_string()),
+    }
+}
+
+/// Replace each word of the case that appears in given map (keyed by
+/// lowercase words) with its abbreviation, e.g. "config_path" with
+/// {"config": "cfg"} -> `Snake("cfg_path")`.
+///
+/// Note that this is lossy, the origin words can't be recovered
+/// from abbreviated ones.
+pub fn abbreviate(
+    case: &NamingCase,
+    abbreviations: &HashMap<String, String>,
+) -> NamingCase {
+    let words = case.words();
+    if words.is_empty() {
+        return which_case(&case.to_string());
+    }
+    let words: Vec<&str> = words
+        .iter()
+        .map(|word| abbreviations.get(word).unwrap_or(word).as_str())
+        .collect();
+    which_case(&words.join("_"))
+}
+
+fn capitalize_or_acronym(word: &str, acronyms: &HashSet<String>) -> String {
+    let upper = word.to_uppercase();
+    if acronyms.contains(&upper) {
+        upper
+    } else {
+        capitalize(word)
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod cases_tests {
+    use crate::to_string_vec;
+
+    use super::*;
+
+    #[test]
+    fn check_if_word_is_already_in_case() {
+        let cases = [
+            ("PAGE_SIZE", NamingCaseKind::ScreamingSnake),
+            ("page_size", NamingCaseKind::Snake),
+            ("page-size", NamingCaseKind::Kebab),
+            ("pageSize", NamingCaseKind::Camel),
+            ("PageSize", NamingCaseKind::Pascal),
+            ("Page-Size2", NamingCaseKind::Train),
+            ("page.size", NamingCaseKind::Dot),
+            ("pagesize", NamingCaseKind::Flat),
+            ("PAGESIZE", NamingCaseKind::UpperFlat),
+            ("Page_Size", NamingCaseKind::Ada),
+            ("PAGE-SIZE", NamingCaseKind::Cobol),
+            ("page/size", NamingCaseKind::Path),
+        ];
+        for (word, kind) in cases.iter() {
+            assert!(is_case(word, *kind), "{} should be {}", word, kind);
+        }
+        assert!(!is_case("pageSize", NamingCaseKind::Snake));
+        assert!(!is_case("Page-size", NamingCaseKind::Train));
+        assert!(!is_case("page..size", NamingCaseKind::Dot));
+        assert!(!is_case("page_size", NamingCaseKind::Flat));
+        assert!(!is_case("", NamingCaseKind::UpperFlat));
+        assert!(!is_case("page_size", NamingCaseKind::Ada));
+        assert!(!is_case("PAGE_SIZE", NamingCaseKind::Ada));
+        assert!(!is_case("Page__Size", NamingCaseKind::Ada));
+        assert!(!is_case("page-size", NamingCaseKind::Cobol));
+        assert!(!is_case("PAGE-SIZE", NamingCaseKind::Kebab));
+        assert!(!is_case("/page/size", NamingCaseKind::Path));
+    }
+
+    #[test]
+    fn repeated_separators_yield_no_empty_words() {
+        let cases = [
+            NamingCase::Snake("a__b".to_string()),
+            NamingCase::Snake("__a_b__".to_string()),
+            NamingCase::Kebab("--a-b".to_string()),
+        ];
+        for case in cases.iter() {
+            assert_eq!(case.words(), to_string_vec(vec!["a", "b"]));
+            assert_eq!(
+                to_camel_with_acronyms(case, &HashSet::new()),
+                Ok("aB".to_string())
+            );
+            assert_eq!(to_dot(case), Ok("a.b".to_string()));
+        }
+        let init = NamingCase::Snake("__init__".to_string());
+        assert_eq!(to_train(&init), Ok("Init".to_string()));
+    }
+
+    #[test]
+    fn kind_of_cases() {
+        assert_eq!(
+            NamingCase::Camel("x".into()).kind(),
+            Some(NamingCaseKind::Camel)
+        );
+        assert_eq!(
+            which_case("PAGE_SIZE").kind(),
+            Some(NamingCaseKind::ScreamingSnake)
+        );
+        assert_eq!(which_case("page").kind(), None);
+        assert_eq!(which_case("-a_").kind(), None);
+        // Display prints the wrapped string.
+        assert_eq!(format!("{}", which_case("page-size")), "page-size");
+    }
+
+    #[test]
+    fn words_of_cases() {
+        let expect = to_string_vec(vec!["snake", "case"]);
+        assert_eq!(which_case("snakeCase").words(), expect);
+        assert_eq!(which_case("snake_case").words(), expect);
+        assert_eq!(which_case("SNAKE_CASE").words(), expect);
+        assert_eq!(which_case("snake-case").words(), expect);
+        assert_eq!(which_case("single").words(), vec!["single"]);
+        assert!(which_case("-invalid_").words().is_empty());
+    }
+
+    #[test]
+    fn names_of_cases() {
+        assert_eq!(which_case("camelCase").case_name(), "camel");
+        assert_eq!(
+            which_case("SCREAMING_SNAKE").case_name(),
+            "screaming_snake"
+        );
+        assert_eq!(which_case("-invalid_").case_name(), "invalid");
+    }
+
+    #[test]
+    fn which_cases_starts_with_which_case() {
+        for word in ["abc", "ABC", "snake_case", "camelCase", "PascalCase"] {
+            assert_eq!(which_cases(word)[0], which_case(word));
+        }
+    }
+
+    #[test]
+    fn which_cases_returns_all_candidates() {
+        let cases = which_cases("abc");
+        assert!(cases.len() > 1);
+        assert!(cases.contains(&NamingCase::Snake("abc".to_string())));
+        assert!(cases.contains(&NamingCase::Kebab("abc".to_string())));
+        assert_eq!(
+            which_cases("snake_case"),
+            vec![NamingCase::Snake("snake_case".to_string())]
+        );
+    }
+
+    #[test]
+    fn which_cases_returns_empty_vec_for_invalid_word() {
+        assert_eq!(which_cases("-invalid_"), Vec::<NamingCase>::new());
+    }
+
+    #[test]
+    fn convert_one_string() {
+        assert_eq!(convert("pageSize", NamingCaseKind::Snake), "page_size");
+        assert_eq!(
+            convert("page_size", NamingCaseKind::ScreamingSnake),
+            "PAGE_SIZE"
+        );
+        assert_eq!(convert("PAGE_SIZE", NamingCaseKind::Train), "Page-Size");
+        assert_eq!(convert("-invalid_", NamingCaseKind::Camel), "-invalid_");
+    }
+
+    #[test]
+    fn convert_unicode_words() {
+        assert_eq!(
+            which_case("straße_name"),
+            NamingCase::Snake("straße_name".to_string())
+        );
+        assert_eq!(
+            which_case("nomRéseau"),
+            NamingCase::Camel("nomRéseau".to_string())
+        );
+        assert_eq!(
+            which_case("ÉtatCivil"),
+            NamingCase::Pascal("ÉtatCivil".to_string())
+        );
+
+        assert_eq!(
+            convert("straße_name", NamingCaseKind::Pascal),
+            "StraßeName"
+        );
+        assert_eq!(convert("straße_name", NamingCaseKind::Camel), "straßeName");
+        // "ß" is uppercased to "SS".
+        assert_eq!(
+            convert("straße_name", NamingCaseKind::ScreamingSnake),
+            "STRASSE_NAME"
+        );
+        assert_eq!(convert("nomRéseau", NamingCaseKind::Snake), "nom_réseau");
+        assert_eq!(convert("ÉtatCivil", NamingCaseKind::Kebab), "état-civil");
+    }
+
+    #[test]
+    fn convert_to_all_kinds() {
+        let all = which_case("pageSize").to_all();
+        assert_eq!(
+            all.keys().copied().collect::<Vec<NamingCaseKind>>(),
+            NamingCaseKind::ALL.to_vec()
+        );
+        assert_eq!(all[&NamingCaseKind::Snake], "page_size");
+        assert_eq!(all[&NamingCaseKind::Path], "page/size");
+
+        let invalid = which_case("-invalid_").to_all();
+        assert_eq!(invalid.len(), NamingCaseKind::ALL.len());
+        assert!(invalid.values().all(|value| value == "-invalid_"));
+    }
+
+    #[test]
+    fn keep_lone_acronyms_in_camel_and_pascal() {
+        let acronyms = HashSet::new();
+        for word in ["ID", "URL", "IO"] {
+            let case = which_case(word);
+            assert_eq!(to_camel_with_acronyms(&case, &acronyms).unwrap(), word);
+            assert_eq!(
+                to_pascal_with_acronyms(&case, &acronyms).unwrap(),
+                word
+            );
+            assert_eq!(case.to_snake().unwrap(), word.to_lowercase());
+        }
+        assert_eq!(convert("URL", NamingCaseKind::Kebab), "url");
+        assert_eq!(convert("IO", NamingCaseKind::ScreamingSnake), "IO");
+
+        assert!(is_acronym("MD5"));
+        for word in ["A", "Id", "id", "5G", "USER_ID"] {
+            assert!(!is_acronym(word));
+        }
+    }
+
+    #[test]
+    fn tag_acronym_words() {
+        assert_eq!(
+            which_case("parseURLValue").tagged_words(),
+            vec![
+                ("parse".to_string(), false),
+                ("url".to_string(), true),
+                ("value".to_string(), false)
+            ]
+        );
+        // tagged in the origin string, whatever naming_lib's words are.
+        assert_eq!(
+            which_case("HTTPServerID").tagged_words(),
+            vec![
+                ("http".to_string(), true),
+                ("server".to_string(), false),
+                ("id".to_string(), true)
+            ]
+        );
+        // uppercase words are acronyms only when glued.
+        assert!(which_case("PARSE_URL")
+            .tagged_words()
+            .iter()
+            .all(|(_, is_acronym)| !is_acronym));
+    }
+
+    #[test]
+    fn render_acronyms_in_styles() {
+        let none = HashSet::new();
+        let camel = |style| {
+            let (case, acronyms) =
+                with_acronym_style(&which_case("parseURL"), &none, style);
+            to_camel_with_acronyms(&case, &acronyms).unwrap()
+        };
+        assert_eq!(camel(AcronymStyle::Upper), "parseURL");
+        assert_eq!(camel(AcronymStyle::Title), "parseUrl");
+        assert_eq!(camel(AcronymStyle::Lower), "parseurl");
+
+        let (case, acronyms) = with_acronym_style(
+            &which_case("HTTPServerID"),
+            &none,
+            AcronymStyle::Upper,
+        );
+        assert_eq!(
+            to_train_with_acronyms(&case, &acronyms).unwrap(),
+            "HTTP-Server-ID"
+        );
+        assert!("x".parse::<AcronymStyle>().is_err());
+    }
+
+    #[test]
+    fn detect_case_conflicts() {
+        let conflict = case_conflict("foo_barBaz").unwrap();
+        assert_eq!(conflict.separators, vec!['_']);
+        assert_eq!(conflict.humped_segments, vec!["barBaz"]);
+        assert_eq!(conflict.words, vec!["foo", "bar", "baz"]);
+        assert_eq!(
+            conflict.to_string(),
+            "separated segments with camel humps: `barBaz`"
+        );
+
+        let conflict = case_conflict("get-HTTPResponse_body").unwrap();
+        assert_eq!(conflict.words, vec!["get", "http", "response", "body"]);
+        assert_eq!(
+            conflict.to_string(),
+            "separators `-`, `_` are mixed; \
+            separated segments with camel humps: `HTTPResponse`"
+        );
+
+        for word in
+            &["fooBar", "foo_bar", "FOO_BAR", "Foo-Bar", "Foo_Bar", "foo"]
+        {
+            assert!(is_consistent(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn convert_with_case_spec() {
+        let case = which_case("parse_url_value");
+        let acronyms: HashSet<String> =
+            vec!["URL".to_string()].into_iter().collect();
+        let spec: CaseSpec = "custom:-:upper".parse().unwrap();
+        assert_eq!(spec, CaseSpec::new("-", WordCase::Upper));
+        assert_eq!(
+            to_custom(&case, &spec, &acronyms).unwrap(),
+            "PARSE-URL-VALUE"
+        );
+
+        let spec: CaseSpec = "custom::title:lower".parse().unwrap();
+        assert_eq!(spec, CaseSpec::preset(NamingCaseKind::Camel));
+        assert_eq!(
+            to_custom(&case, &spec, &acronyms).unwrap(),
+            "parseURLValue"
+        );
+
+        for value in
+            &["custom:-", "custom:-:upper:x", "custom:a:b:c:d", "-:upper"]
+        {
+            assert!(value.parse::<CaseSpec>().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn presets_agree_with_naming_lib() {
+        let no_acronyms = HashSet::new();
+        for word in &["pageSize", "user_id", "MAX_VALUE", "http-request"] {
+            let case = which_case(word);
+            let preset = |kind| {
+                to_custom(&case, &CaseSpec::preset(kind), &no_acronyms).unwrap()
+            };
+            assert_eq!(
+                preset(NamingCaseKind::ScreamingSnake),
+                case.to_screaming_snake().unwrap()
+            );
+            assert_eq!(preset(NamingCaseKind::Snake), case.to_snake().unwrap());
+            assert_eq!(preset(NamingCaseKind::Kebab), case.to_kebab().unwrap());
+        }
+    }
+
+    #[test]
+    fn explain_classification() {
+        let explanation = explain("pageSize");
+        assert_eq!(explanation.case, which_case("pageSize"));
+        assert_eq!(explanation.words, vec!["page", "size"]);
+        assert!(explanation.alternatives.is_empty());
+        assert_eq!(
+            explanation.to_string(),
+            "pageSize
+  case: camel
+  words: page, size
+  also valid: -
+  not screaming_snake: has lowercase letters
+  not snake: has uppercase letters
+  not kebab: has uppercase letters
+  not pascal: doesn't start with an uppercase letter"
+        );
+
+        let explanation = explain("page-size_x");
+        assert_eq!(explanation.case.case_name(), "invalid");
+        assert!(explanation
+            .rejected
+            .contains(&("snake", "has separators other than `_`")));
+        assert!(explanation.rejected.contains(&("camel", "has separators")));
+    }
+
+    #[test]
+    fn digits_attach_to_previous_words() {
+        for (word, words, snake) in &[
+            ("parse2Json", vec!["parse2", "json"], "parse2_json"),
+            ("utf8Decoder", vec!["utf8", "decoder"], "utf8_decoder"),
+            (
+                "getHTTP2Response",
+                vec!["get", "http2", "response"],
+                "get_http2_response",
+            ),
+            ("VERSION_2_CASE", vec!["version2", "case"], "version2_case"),
+            ("2_fast", vec!["2", "fast"], "2_fast"),
+        ] {
+            let case = which_case(word);
+            assert_eq!(case.words(), *words);
+            assert_eq!(to_snake(&case).unwrap(), *snake);
+        }
+
+        // digits keep their word through a round trip.
+        let camel = convert("VERSION_2_CASE", NamingCaseKind::Camel);
+        assert_eq!(camel, "version2Case");
+        assert_eq!(
+            convert(&camel, NamingCaseKind::ScreamingSnake),
+            "VERSION2_CASE"
+        );
+        assert_eq!(convert("parse2_json", NamingCaseKind::Camel), "parse2Json");
+    }
+
+    #[test]
+    fn split_words_on_acronym_boundaries() {
+        for (word, words) in &[
+            ("HTTPServer", vec!["HTTP", "Server"]),
+            ("getHTTPSPage", vec!["get", "HTTPS", "Page"]),
+            ("XMLHttpRequest", vec!["XML", "Http", "Request"]),
+            ("HTTP2Server", vec!["HTTP2", "Server"]),
+            ("utf8String", vec!["utf8", "String"]),
+            ("parseURL", vec!["parse", "URL"]),
+            ("pageSize", vec!["page", "Size"]),
+        ] {
+            assert_eq!(split_acronym_words(word), *words);
+        }
+
+        // every format is built from these words.
+        for (word, snake, kebab) in &[
+            ("HTTPServer", "http_server", "http-server"),
+            ("getHTTPSPage", "get_https_page", "get-https-page"),
+            ("XMLHttpRequest", "xml_http_request", "xml-http-request"),
+            ("IOError", "io_error", "io-error"),
+        ] {
+            let case = which_case(word);
+            assert_eq!(to_snake(&case).unwrap(), *snake);
+            assert_eq!(to_kebab(&case).unwrap(), *kebab);
+
+            let merged = merge_acronyms(&case);
+            assert_eq!(to_snake(&merged).unwrap(), *snake);
+        }
+    }
+
+    #[test]
+    fn abbreviate_words() {
+        let abbreviations: HashMap<String, String> =
+            [("config", "cfg"), ("message", "msg")]
+                .iter()
+                .map(|(word, abbr)| (word.to_string(), abbr.to_string()))
+                .collect();
+        let case = abbreviate(&which_case("configPath"), &abbreviations);
+        assert_eq!(case.to_camel().unwrap(), "cfgPath");
+        let case = abbreviate(&which_case("ERROR_MESSAGE"), &abbreviations);
+        assert_eq!(case.to_screaming_snake().unwrap(), "ERROR_MSG");
+    }
+
+    #[test]
+    fn parse_and_display_kinds() {
+        for kind in NamingCaseKind::ALL {
+            assert_eq!(kind.letter().parse::<NamingCaseKind>().unwrap(), kind);
+            assert_eq!(
+                kind.to_string().parse::<NamingCaseKind>().unwrap(),
+                kind
+            );
+        }
+        assert_eq!(NamingCaseKind::UpperFlat.to_string(), "upper_flat");
+        assert_eq!(
+            "screaming-snake".parse::<NamingCaseKind>().unwrap(),
+            NamingCaseKind::ScreamingSnake
+        );
+        assert!("x".parse::<NamingCaseKind>().is_err());
+    }
+
+    #[test]
+    fn check_valid_identifiers() {
+        for word in ["abc", "ABC", "snake_case", "camelCase", "kebab-case"] {
+            assert!(is_valid_identifier(word));
+        }
+        for word in ["-invalid_", "a b", "_", ""] {
+            assert!(!is_valid_identifier(word));
+        }
+    }
+
+    #[test]
+    fn convert_to_train() {
+        assert_eq!(
+            to_train(&which_case("content_type")).unwrap(),
+            "Content-Type"
+        );
+        assert_eq!(
+            to_train(&which_case("xForwardedFor")).unwrap(),
+            "X-Forwarded-For"
+        );
+        assert_eq!(to_train(&which_case("single")).unwrap(), "Single");
+    }
+
+    #[test]
+    fn convert_to_ada() {
+        assert_eq!(to_ada(&which_case("snake_case")).unwrap(), "Snake_Case");
+        assert_eq!(
+            to_ada(&which_case("xForwardedFor")).unwrap(),
+            "X_Forwarded_For"
+        );
+        assert!(is_ada("Snake_Case"));
+    }
+
+    #[test]
+    fn convert_to_cobol() {
+        assert_eq!(
+            to_cobol(&which_case("customer_id")).unwrap(),
+            "CUSTOMER-ID"
+        );
+        assert!(is_cobol("CUSTOMER-ID"));
+    }
+
+    #[test]
+    fn tell_strict_camel_case_from_lenient() {
+        for word in ["pageSize", "md5Sum", "getHTTPResponse"] {
+            assert!(is_camel_strict(word));
+            assert!(is_camel_lenient(word));
+        }
+        // single words are only lenient camel case.
+        assert!(!is_camel_strict("page"));
+        assert!(is_camel_lenient("page"));
+        for word in ["PageSize", "page_Size", "2fa", ""] {
+            assert!(!is_camel_strict(word));
+        }
+    }
+
+    #[test]
+    fn convert_to_path_and_back() {
+        let path = to_path(&which_case("user_profile_page")).unwrap();
+        assert_eq!(path, "user/profile/page");
+        let case = from_path(&path).unwrap();
+        assert_eq!(case.to_kebab().unwrap(), "user-profile-page");
+        assert_eq!(to_path(&case).unwrap(), path);
+        assert!(from_path("user_profile").is_none());
+    }
+
+    #[test]
+    fn split_qualified_names() {
+        assert_eq!(
+            split_qualified("ns::Type.value"),
+            Some((vec!["ns", "Type", "value"], vec!["::", "."]))
+        );
+        assert_eq!(
+            split_qualified("obj.method"),
+            Some((vec!["obj", "method"], vec!["."]))
+        );
+        for word in ["plain_word", "a..b", ".a", "a::", "a:b"] {
+            assert!(split_qualified(word).is_none());
+        }
+    }
+
+    #[test]
+    fn convert_to_dot() {
+        assert_eq!(to_dot(&which_case("snake_case")).unwrap(), "snake.case");
+        assert_eq!(
+            to_dot(&which_case("userFirstName")).unwrap(),
+            "user.first.name"
+        );
+    }
+
+    #[test]
+    fn convert_to_flat() {
+        assert_eq!(to_flat(&which_case("PascalCase")).unwrap(), "pascalcase");
+        assert_eq!(to_flat(&which_case("kebab-case")).unwrap(), "kebabcase");
+    }
+
+    #[test]
+    fn convert_with_acronyms() {
+        let acronyms: HashSet<String> =
+            vec!["URL".to_string(), "ID".to_string()].into_iter().collect();
+        let case = which_case("parse_url_id");
+        assert_eq!(
+            to_camel_with_acronyms(&case, &acronyms).unwrap(),
+            "parseURLID"
+        );
+        assert_eq!(
+            to_pascal_with_acronyms(&case, &acronyms).unwrap(),
+            "ParseURLID"
+        );
+    }
+
+    #[test]
+    fn convert_without_acronyms_is_same_as_plain_conversion() {
+        let case = which_case("parse_url_id");
+        let acronyms = HashSet::new();
+        assert_eq!(to_camel_with_acronyms(&case, &acronyms), case.to_camel());
+        assert_eq!(to_pascal_with_acronyms(&case, &acronyms), case.to_pascal());
+    }
+
+    #[test]
+    fn strip_multi_letter_hungarian_prefix() {
+        let prefixes: HashSet<String> =
+            vec!["lpsz".to_string(), "dw".to_string()].into_iter().collect();
+        assert_eq!(
+            from_hungarian_notation_with_prefixes("lpszUserName", &prefixes),
+            NamingCase::Pascal("UserName".to_string())
+        );
+        assert_eq!(
+            from_hungarian_notation_with_prefixes("dwCount", &prefixes),
+            which_case("Count")
+        );
+        // not a known prefix, same as naming_lib's behavior.
+        assert_eq!(
+            from_hungarian_notation_with_prefixes("intPageSize", &prefixes),
+            naming_lib::from_hungarian_notation("intPageSize")
+        );
+    }
+
+    #[test]
+    fn convert_to_upper_flat() {
+        assert_eq!(
+            to_upper_flat(&which_case("snake_case")).unwrap(),
+            "SNAKECASE"
+        );
+        assert_eq!(
+            to_upper_flat(&which_case("kebab-case")).unwrap(),
+            "KEBABCASE"
+        );
+    }
+
+    #[test]
+    fn roundtrip_between_formats_is_stable() {
+        let corpus = vec![
+            "page_size",
+            "PAGE_SIZE",
+            "page-size",
+            "pageSize",
+            "PageSize",
+            "x_forwarded_for",
+            "single",
+        ];
+        let acronyms = HashSet::new();
+        // formats that can be recognized again by which_case.
+        for kind in &NamingCaseKind::ALL[..5] {
+            for word in &corpus {
+                let case = which_case(word);
+                let converted = kind.convert(&case, &acronyms).unwrap();
+                assert_eq!(
+                    which_case(&converted).words(),
+                    case.words(),
+                    "{} -> {} -> words",
+                    word,
+                    converted
+                );
+            }
+        }
+    }
+}
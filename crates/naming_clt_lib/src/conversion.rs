@@ -1,25 +1,130 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::str::FromStr;
 
-use naming_lib::{self as naming, NamingCase};
+use naming_lib::NamingCase;
+use unicode_width::UnicodeWidthStr;
 
-use super::Formatter;
+use super::{
+    AcronymStyle, Captor, CaseMapper, CaseSpec, ConversionRegistry, Format,
+    InputStats, NamingCaseExt, NamingCaseKind, NamingError,
+};
 
 /// Answer user's `--filter` option,
 /// ignore captured words that user not indicates in `--filter` option,
 /// and convert words to NamingCase instances.
 pub struct Filter {
     options: Vec<String>,
+    excluded: Vec<String>,
+    hungarian_prefixes: HashSet<String>,
+    stripped_prefix: Option<String>,
+    // how many letters may have a wrong case, see `with_tolerance`.
+    tolerance: usize,
 }
 
 impl Filter {
-    pub fn new(options: Option<Vec<String>>) -> Result<Filter, &'static str> {
-        let options = options.unwrap_or_else(|| super::DEFAULT_OPTIONS.clone());
-
+    /// Return an Err if there is an unknown format letter in options,
+    /// or both hungarian notation (h) and camel case (c) are in options.
+    pub fn new(options: Option<Vec<String>>) -> Result<Filter, NamingError> {
+        let mut options =
+            options.unwrap_or_else(|| super::DEFAULT_OPTIONS.clone());
+        // an empty option matches no format.
+        options.retain(|option| !option.is_empty());
+
+        if let Some(unknown) = Filter::find_unknown_option(&options) {
+            return Err(NamingError::InvalidFilterLetter {
+                letter: unknown.to_string(),
+                valid: Filter::PREDICATES
+                    .iter()
+                    .map(|p| p.name.to_string())
+                    .collect(),
+            });
+        }
         if Filter::has_hungarian_camel_conflict(&options) {
-            return Err("naming: In option --filter, at most one of the two, \
-            hungarian notation (h) and camel case (c) can appear.");
+            return Err(NamingError::FilterConflict);
+        }
+        Ok(Filter {
+            options,
+            excluded: Vec::new(),
+            hungarian_prefixes: HashSet::new(),
+            stripped_prefix: None,
+            tolerance: 0,
+        })
+    }
+
+    /// Answer user's `--filter-out` option,
+    /// words that match one of the excluded formats are discarded,
+    /// even if they also match a format that is still allowed
+    /// (e.g. "abc" is valid in both snake and kebab case).
+    pub fn excluding(mut self, excluded: Option<Vec<String>>) -> Filter {
+        let excluded = excluded.unwrap_or_default();
+        self.options.retain(|option| !excluded.contains(option));
+        self.excluded = excluded;
+        self
+    }
+
+    /// Answer user's `--hungarian-prefixes` option,
+    /// multi-letter type prefixes (e.g. "lpsz", "dw") that will be
+    /// stripped as a whole when treating words as hungarian notation.
+    pub fn with_hungarian_prefixes(
+        mut self,
+        prefixes: Option<Vec<String>>,
+    ) -> Filter {
+        self.hungarian_prefixes =
+            prefixes.unwrap_or_default().into_iter().collect();
+        self
+    }
+
+    /// Answer user's `--strip-prefix` option, a literal prefix
+    /// (e.g. "m_" in "m_fooBar") removed from each word before filtering
+    /// and classifying, words without this prefix are kept unchanged.
+    pub fn with_stripped_prefix(mut self, prefix: Option<&str>) -> Filter {
+        self.stripped_prefix = prefix.map(|prefix| prefix.to_string());
+        self
+    }
+
+    /// Answer user's `--fuzzy-filter` option, a word that matches none
+    /// of the formats still passes the filter, if flipping the case of
+    /// at most `tolerance` letters makes it match one of them,
+    /// e.g. "user_Id" passes snake case with tolerance 1.
+    /// Such a word is classified and converted as the corrected one,
+    /// e.g. "user_id", which is also checked against excluded formats.
+    ///
+    /// Only the case of letters is tolerated: any letter of screaming
+    /// snake, snake and kebab case, but only the first letter of
+    /// camel and pascal case, as their other capitals are word boundaries.
+    /// The format needing the fewest flips wins, e.g. "PageSize" is
+    /// corrected to "pageSize" rather than "pagesize" in snake case.
+    /// The default tolerance 0 keeps the filter strict.
+    pub fn with_tolerance(mut self, tolerance: usize) -> Filter {
+        self.tolerance = tolerance;
+        self
+    }
+
+    fn strip_prefix(&self, word: String) -> String {
+        match &self.stripped_prefix {
+            // keep the word if nothing remains after stripping.
+            Some(prefix) if word.len() > prefix.len() => {
+                match word.strip_prefix(prefix.as_str()) {
+                    Some(stripped) => stripped.to_string(),
+                    None => word,
+                }
+            }
+            _ => word,
         }
-        Ok(Filter { options })
+    }
+
+    /// The part of given word that `with_stripped_prefix` removes,
+    /// empty if nothing is removed.
+    pub fn stripped_prefix_of<'a>(&self, word: &'a str) -> &'a str {
+        let stripped = self.strip_prefix(word.to_string());
+        &word[..word.len() - stripped.len()]
+    }
+
+    fn find_unknown_option(options: &[String]) -> Option<&String> {
+        options.iter().find(|option| {
+            !Filter::PREDICATES.iter().any(|p| p.name == option.as_str())
+        })
     }
 
     fn has_hungarian_camel_conflict(options: &[String]) -> bool {
@@ -29,34 +134,136 @@ impl Filter {
     /// Not only transform String to NamingCase,
     /// but also apply given filter on result vector.
     pub fn to_naming_cases_from(&self, words: Vec<String>) -> Vec<NamingCase> {
+        let words =
+            words.into_iter().map(|word| self.strip_prefix(word)).collect();
         let words = self.filter_words_with_options(words);
         self.convert_words_to_naming_cases(words)
     }
 
-    fn filter_words_with_options(&self, mut words: Vec<String>) -> Vec<String> {
-        let predicates: Vec<fn(&str) -> bool> = Filter::PREDICATES
-            .iter()
-            .filter(|predicate| {
-                self.options.contains(&predicate.name.to_string())
+    /// Same as `to_naming_cases_from`, but for a single word,
+    /// return `None` if the word is filtered out.
+    pub fn to_naming_case(&self, word: &str) -> Option<NamingCase> {
+        self.to_naming_cases_from(vec![word.to_string()]).pop()
+    }
+
+    fn filter_words_with_options(&self, words: Vec<String>) -> Vec<String> {
+        let predicates = Filter::select_predicates(&self.options);
+        let excluded = Filter::select_predicates(&self.excluded);
+
+        let passes = |word: &str| {
+            Filter::is_one_of_formats(&predicates, word)
+                && !Filter::is_one_of_formats(&excluded, word)
+        };
+        // the corrected word within tolerance, see `with_tolerance`.
+        let correct = |word: &str| {
+            if self.tolerance == 0 {
+                return None;
+            }
+            predicates
+                .iter()
+                .map(|p| {
+                    let corrected = (p.correct)(word);
+                    let flipped = word
+                        .chars()
+                        .zip(corrected.chars())
+                        .filter(|(a, b)| a != b)
+                        .count();
+                    (flipped, corrected)
+                })
+                .filter(|(flipped, corrected)| {
+                    *flipped <= self.tolerance && passes(corrected)
+                })
+                .min_by_key(|(flipped, _)| *flipped)
+                .map(|(_, corrected)| corrected)
+        };
+        let accept = |word: &str| {
+            if passes(word) {
+                Some(word.to_string())
+            } else {
+                correct(word)
+            }
+        };
+        words
+            .into_iter()
+            .filter_map(|word| match super::split_qualified(&word) {
+                // every segment of a qualified name should pass.
+                Some((segments, separators)) => {
+                    let mut accepted = accept(segments[0])?;
+                    for (segment, separator) in
+                        segments[1..].iter().zip(separators)
+                    {
+                        accepted.push_str(separator);
+                        accepted.push_str(&accept(segment)?);
+                    }
+                    Some(accepted)
+                }
+                // a path/case word passes as its snake case form.
+                None if word.contains('/') => {
+                    let snake = word.replace('/', "_");
+                    if super::is_path(&word) && passes(&snake) {
+                        Some(word)
+                    } else {
+                        None
+                    }
+                }
+                None if passes(&word) => Some(word),
+                None => correct(&word),
             })
-            .map(|predicate| predicate.function)
-            .collect();
+            .collect()
+    }
 
-        words.retain(|word| Filter::is_one_of_formats(&predicates, word));
+    /// Answer user's `--show-rejected` option,
+    /// return the words that would be discarded by this filter.
+    pub fn rejected_words(&self, words: &[String]) -> Vec<String> {
         words
+            .iter()
+            .filter(|word| self.to_naming_case(word).is_none())
+            .cloned()
+            .collect()
+    }
+
+    fn select_predicates(options: &[String]) -> Vec<&'static Predicate> {
+        Filter::PREDICATES
+            .iter()
+            .filter(|predicate| options.contains(&predicate.name.to_string()))
+            .collect()
     }
 
     const PREDICATES: [Predicate; 6] = [
-        Predicate { name: "S", function: naming::is_screaming_snake },
-        Predicate { name: "s", function: naming::is_snake },
-        Predicate { name: "k", function: naming::is_kebab },
-        Predicate { name: "c", function: naming::is_camel },
-        Predicate { name: "h", function: naming::is_camel },
-        Predicate { name: "p", function: naming::is_pascal },
+        Predicate {
+            name: "S",
+            function: super::is_screaming_snake,
+            correct: str::to_uppercase,
+        },
+        Predicate {
+            name: "s",
+            function: super::is_snake,
+            correct: str::to_lowercase,
+        },
+        Predicate {
+            name: "k",
+            function: super::is_kebab,
+            correct: str::to_lowercase,
+        },
+        Predicate {
+            name: "c",
+            function: super::is_camel_strict,
+            correct: lowercase_first_letter,
+        },
+        Predicate {
+            name: "h",
+            function: super::is_camel_lenient,
+            correct: lowercase_first_letter,
+        },
+        Predicate {
+            name: "p",
+            function: super::is_pascal,
+            correct: uppercase_first_letter,
+        },
     ];
 
-    fn is_one_of_formats(predicates: &[fn(&str) -> bool], word: &str) -> bool {
-        predicates.iter().map(|f| f(word)).reduce(|a, b| a || b).unwrap()
+    fn is_one_of_formats(predicates: &[&Predicate], word: &str) -> bool {
+        predicates.iter().any(|p| (p.function)(word))
     }
 
     fn convert_words_to_naming_cases(
@@ -68,10 +275,19 @@ impl Filter {
         words
             .iter()
             .map(|word| {
-                if required_hungarian && naming::is_camel(word) {
-                    naming::from_hungarian_notation(word)
+                if super::split_qualified(word).is_some() || word.contains('/')
+                {
+                    // invalid as one identifier, formats convert it
+                    // segment by segment (or as a path/case word),
+                    // see `Format::convert`.
+                    NamingCase::Invalid(word.clone())
+                } else if required_hungarian && super::is_camel_lenient(word) {
+                    super::from_hungarian_notation_with_prefixes(
+                        word,
+                        &self.hungarian_prefixes,
+                    )
                 } else {
-                    naming::which_case(word)
+                    super::which_case(word)
                 }
             })
             .collect()
@@ -83,6 +299,25 @@ impl Filter {
 struct Predicate {
     pub name: &'static str,
     pub function: fn(&str) -> bool,
+    // flip the case of letters that could make the word match,
+    // see `Filter::with_tolerance`.
+    pub correct: fn(&str) -> String,
+}
+
+fn lowercase_first_letter(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn uppercase_first_letter(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
 
 /// Take `--output` option's value,
@@ -91,15 +326,276 @@ struct Predicate {
 pub struct Convertor {
     options: Vec<String>,
     cases: Vec<NamingCase>,
+    registry: ConversionRegistry,
+    // prefix and suffix added to each converted string.
+    affixes: (String, String),
+    acronyms: HashSet<String>,
+    acronym_style: AcronymStyle,
+    abbreviations: HashMap<String, String>,
+    merge_acronyms: bool,
+    json_keys: JsonKeys,
+    // header row of csv, tsv and table outputs.
+    header: bool,
+    // prefix and suffix wrapped around OR-joined regex.
+    regex_bounds: (String, String),
+    regex_icase: bool,
+    // maximum columns of each line of a regex in extended mode.
+    regex_wrap: Option<usize>,
+    mark_unchanged: bool,
+    // words discarded by filter, only reported in json outputs.
+    rejected: Option<Vec<String>>,
+    // formats of options with the options above applied, rebuilt by
+    // each builder that changes them, see `with_mappers_rebuilt`.
+    mappers: Vec<Box<dyn CaseMapper>>,
 }
 
 impl Convertor {
+    /// Omitted (or empty) options default to the first 5 formats,
+    /// in a fixed order: S, s, k, c, p.
     pub fn new(
         options: Option<Vec<String>>,
         cases: Vec<NamingCase>,
     ) -> Convertor {
-        let options = options.unwrap_or_else(|| super::DEFAULT_OPTIONS.clone());
-        Convertor { options, cases }
+        let options = options
+            .filter(|options| !options.is_empty())
+            .unwrap_or_else(|| super::DEFAULT_OPTIONS.clone());
+        Convertor {
+            options,
+            cases,
+            registry: ConversionRegistry::default(),
+            affixes: (String::new(), String::new()),
+            acronyms: HashSet::new(),
+            acronym_style: AcronymStyle::default(),
+            abbreviations: HashMap::new(),
+            merge_acronyms: false,
+            json_keys: JsonKeys::default(),
+            header: true,
+            regex_bounds: (String::new(), String::new()),
+            regex_icase: false,
+            regex_wrap: None,
+            mark_unchanged: false,
+            rejected: None,
+            mappers: Vec::new(),
+        }
+        .with_mappers_rebuilt()
+    }
+
+    /// Build a convertor from words that are already extracted,
+    /// e.g. tokens of an embedder's own parser, skipping the `Captor`:
+    /// the words go through the filter (see `Filter::to_naming_cases_from`),
+    /// options are same as `new`'s.
+    ///
+    /// ```text
+    /// let filter = Filter::new(None)?;
+    /// let words = to_string_vec(vec!["pageSize", "user_id"]);
+    /// let output = Convertor::from_words(None, words, &filter).into_lines();
+    /// ```
+    pub fn from_words(
+        options: Option<Vec<String>>,
+        words: Vec<String>,
+        filter: &Filter,
+    ) -> Convertor {
+        Convertor::new(options, filter.to_naming_cases_from(words))
+    }
+
+    /// Use given registry to look up formats of options,
+    /// instead of the default one with built-in formats only.
+    pub fn with_registry(mut self, registry: ConversionRegistry) -> Convertor {
+        self.registry = registry;
+        self.with_mappers_rebuilt()
+    }
+
+    /// Check that every option is a format in the registry, return an Err
+    /// naming the unknown option and listing valid ones otherwise.
+    /// Call it after `with_registry`, as custom formats are valid options.
+    pub fn validated(self) -> Result<Convertor, NamingError> {
+        let unknown =
+            self.options.iter().find(|option| self.format_of(option).is_none());
+        match unknown {
+            None => Ok(self),
+            Some(unknown) => Err(self.unknown_format(unknown)),
+        }
+    }
+
+    /// The error of an option that `format_of` can't resolve.
+    fn unknown_format(&self, option: &str) -> NamingError {
+        // report why a custom format can't be parsed.
+        if let Err(err) = option.parse::<CaseSpec>() {
+            if option.starts_with("custom:") {
+                return err;
+            }
+        }
+        NamingError::InvalidOutputFormat {
+            format: option.to_string(),
+            valid: self
+                .registry
+                .formats()
+                .map(|(key, _)| key.to_string())
+                .collect(),
+        }
+    }
+
+    /// Answer user's `--prefix` and `--suffix` options, decorate
+    /// each converted string (but not the origin string),
+    /// e.g. "get_" prefix: "userName" -> "get_user_name".
+    pub fn with_affixes(
+        mut self,
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+    ) -> Convertor {
+        self.affixes = (
+            prefix.unwrap_or_default().to_string(),
+            suffix.unwrap_or_default().to_string(),
+        );
+        self.with_mappers_rebuilt()
+    }
+
+    /// Whether there is no case to convert,
+    /// e.g. all captures are filtered out.
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+
+    /// Answer user's `--abbreviations` option, replace words with their
+    /// abbreviations (see `abbreviate`) in converted strings,
+    /// e.g. {"config": "cfg"}: "config_path" -> "cfgPath" in camel case.
+    pub fn with_abbreviations(
+        mut self,
+        abbreviations: HashMap<String, String>,
+    ) -> Convertor {
+        self.abbreviations = abbreviations;
+        self.with_mappers_rebuilt()
+    }
+
+    /// Answer user's `--merge-acronyms` option, rebuild camel and pascal
+    /// cases from their words before converting, see `merge_acronyms`.
+    /// Acronyms are one word anyway, so the output is the same.
+    pub fn with_acronyms_merged(mut self, merge: bool) -> Convertor {
+        self.merge_acronyms = merge;
+        self.with_mappers_rebuilt()
+    }
+
+    /// Answer user's `--json-keys` option, see `JsonKeys`.
+    pub fn with_json_keys(mut self, keys: JsonKeys) -> Convertor {
+        self.json_keys = keys;
+        self
+    }
+
+    /// Answer user's `--acronym-style` option, see `AcronymStyle`.
+    pub fn with_acronym_style(mut self, style: AcronymStyle) -> Convertor {
+        self.acronym_style = style;
+        self.with_mappers_rebuilt()
+    }
+
+    /// Answer user's `--show-rejected` option when `--json` is passed,
+    /// the words discarded by filter will be listed
+    /// in a "rejected" array of the output object.
+    pub fn with_rejected(mut self, rejected: Vec<String>) -> Convertor {
+        self.rejected = Some(rejected);
+        self
+    }
+
+    /// Answer user's `--mark-unchanged` option, in lines outputs,
+    /// append "=" to converted strings that are same as the origin string,
+    /// in json output, add a "changed" object that tells whether
+    /// each format's converted string differs from the origin string.
+    pub fn with_unchanged_marked(mut self, mark: bool) -> Convertor {
+        self.mark_unchanged = mark;
+        self
+    }
+
+    /// Answer user's `--header` and `--no-header` options, whether csv, tsv
+    /// and table outputs start with a header row, true by default.
+    /// Turn it off to append rows to an existing file.
+    pub fn with_header(mut self, header: bool) -> Convertor {
+        self.header = header;
+        self
+    }
+
+    /// Answer user's `--regex-icase` option, prefix the regex with
+    /// the inline flag `(?i)` and drop alternatives that are same as
+    /// a former one under case folding (e.g. "PAGE_SIZE" and "page_size").
+    pub fn with_regex_icase(mut self, icase: bool) -> Convertor {
+        self.regex_icase = icase;
+        self
+    }
+
+    /// Answer user's `--wrap` option, output the regex in extended mode
+    /// (with the inline flag `(?x)`), alternatives are spread over lines
+    /// no wider than `width` columns, each indented with two spaces.
+    /// An alternative wider than that is put on a line of its own.
+    pub fn with_regex_wrap(mut self, width: Option<usize>) -> Convertor {
+        self.regex_wrap = width;
+        self
+    }
+
+    /// Answer user's `--regex-anchor` and `--regex-word-boundary` options,
+    /// wrap the OR-joined regex with `^(...)$` and/or `\b(...)\b`,
+    /// so that it won't match a substring of other identifiers.
+    pub fn with_regex_bounds(
+        mut self,
+        anchor: bool,
+        word_boundary: bool,
+    ) -> Convertor {
+        if !anchor && !word_boundary {
+            self.regex_bounds = (String::new(), String::new());
+            return self;
+        }
+        let (anchor_start, anchor_end) =
+            if anchor { ("^", "$") } else { ("", "") };
+        let boundary = if word_boundary { r"\b" } else { "" };
+        self.regex_bounds = (
+            format!("{}{}(", anchor_start, boundary),
+            format!("){}{}", boundary, anchor_end),
+        );
+        self
+    }
+
+    /// Answer user's `--acronyms` option,
+    /// words in this list will be fully uppercased
+    /// when converting to camel case or pascal case.
+    pub fn with_acronyms(mut self, acronyms: Option<Vec<String>>) -> Convertor {
+        self.acronyms = to_acronym_set(acronyms);
+        self.with_mappers_rebuilt()
+    }
+
+    /// Answer user's `--unique` option,
+    /// drop cases with duplicate origin strings, keep the first-seen one.
+    ///
+    /// Captures are already deduplicated by `Captor`, but different captures
+    /// may end up with the same origin after filtering, e.g. "intSize" and
+    /// "strSize" both become "Size" as hungarian notation.
+    pub fn dedup_origins(mut self) -> Convertor {
+        let mut set = HashSet::new();
+        self.cases.retain(|case| set.insert(case.to_string()));
+        self
+    }
+
+    /// Answer user's `--sort` and `--sort-by` options,
+    /// sort cases alphabetically by their origin strings, or by
+    /// their converted strings in given format (one of `--output` values,
+    /// "custom:..." ones included), converted same as in outputs, i.e.
+    /// with affixes, acronyms and abbreviations applied.
+    /// The sort is stable, cases with equal keys keep their input order.
+    ///
+    /// Return an Err same as `validated`'s if the format is unknown.
+    pub fn sort_by(
+        mut self,
+        format: Option<&str>,
+    ) -> Result<Convertor, NamingError> {
+        match format {
+            None => self.cases.sort_by_cached_key(|case| case.to_string()),
+            Some(option) => {
+                let format = self
+                    .configured_format_of(option)
+                    .ok_or_else(|| self.unknown_format(option))?;
+                // unconvertible (invalid) cases are keyed by origin strings.
+                self.cases.sort_by_cached_key(|case| {
+                    format.map(case).unwrap_or_else(|_| case.to_string())
+                });
+            }
+        }
+        Ok(self)
     }
 
     /// Normal output format, each line represent a captures in input text.
@@ -108,44 +604,166 @@ impl Convertor {
     /// \<origin string of capture1\> \<first target naming case\> \<second format\> ...
     /// \<origin string of capture2\> \<first target naming case\> \<second format\> ...
     /// ...
+    ///
+    /// A convenience wrapper of `write_lines` that writes into a `String`.
     pub fn into_lines(self) -> String {
-        let mappers =
-            self.select_mappers_base_on_options(&super::DIRECT_MAPPERS);
+        let mut buffer = Vec::new();
+        // writing into a Vec<u8> never fails.
+        self.write_lines(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    /// Same as `into_lines`, but streams lines to the writer one by one,
+    /// without building the whole joined output in memory.
+    /// Prefer this on large inputs.
+    pub fn write_lines<W: Write>(self, w: &mut W) -> io::Result<()> {
+        let mappers = &self.mappers;
+
+        for (index, case) in self.cases.iter().enumerate() {
+            if index > 0 {
+                w.write_all(b"\n")?;
+            }
+            self.write_one_word_line(w, mappers, case)?;
+        }
+        Ok(())
+    }
 
+    /// Same as `into_lines`, but colorized with ANSI escape codes when user
+    /// enters `--color` option (or by default on terminal):
+    /// the origin string is tinted by its source naming case,
+    /// and each converted string by its target format,
+    /// a format has the same color in both places.
+    pub fn into_colored_lines(self) -> String {
+        let mappers = &self.mappers;
+
+        let options = &self.options;
         self.cases
-            .into_iter()
-            .map(|case| Convertor::one_word_to_line(&mappers, &case))
+            .iter()
+            .map(|case| {
+                let mut line = vec![paint(&case.to_string(), case_color(case))];
+                line.extend(options.iter().zip(mappers.iter()).map(
+                    |(option, f)| {
+                        let color = option
+                            .parse()
+                            .map(|kind| format_color(&kind))
+                            .unwrap_or("0");
+                        let value = f.map(case).unwrap();
+                        let marker = self.unchanged_marker(case, &value);
+                        paint(&value, color) + marker
+                    },
+                ));
+                line.join(" ")
+            })
             .collect::<Vec<String>>()
             .join("\n")
     }
 
-    fn select_mappers_base_on_options(
-        &self,
-        mappers: &HashMap<&'static str, Formatter>,
-    ) -> Box<[Formatter]> {
+    /// The registry key of given option, built-in formats can also be
+    /// given by names (e.g. "snake" or "screaming-snake" for "s" and "S"),
+    /// unless the name itself is registered as a key.
+    fn key_of<'a>(&self, option: &'a str) -> &'a str {
+        if self.registry.get(option).is_some() {
+            return option;
+        }
+        match option.parse::<NamingCaseKind>() {
+            Ok(kind) => kind.letter(),
+            Err(_) => option,
+        }
+    }
+
+    /// The registered format of given option,
+    /// or a custom one if it's a "custom:..." value, see `CaseSpec`.
+    fn format_of(&self, option: &str) -> Option<Format> {
+        match self.registry.get(self.key_of(option)) {
+            Some(format) => Some(format.clone()),
+            None => {
+                let spec = option.parse::<CaseSpec>().ok()?;
+                Some(Format::custom(option, spec))
+            }
+        }
+    }
+
+    /// Keys of converted strings in json outputs, in the same order
+    /// as mappers, see `JsonKeys`.
+    fn json_keys(&self) -> Vec<String> {
+        self.options
+            .iter()
+            .filter_map(|option| {
+                let format = self.format_of(option)?;
+                Some(match self.json_keys {
+                    JsonKeys::Snake => format.name().to_string(),
+                    JsonKeys::Camel => {
+                        super::convert(format.name(), NamingCaseKind::Camel)
+                    }
+                    JsonKeys::Short => self.key_of(option).to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn with_mappers_rebuilt(mut self) -> Convertor {
+        self.mappers = self.select_mappers_base_on_options();
+        self
+    }
+
+    fn select_mappers_base_on_options(&self) -> Vec<Box<dyn CaseMapper>> {
         // let the order of mappers to be same as
         // the order of options in vector.
+        // unknown options are reported by `validated`, skip them here.
         self.options
             .iter()
-            .map(|option| *mappers.get(option.as_str()).unwrap())
+            .filter_map(|option| {
+                let mapper = self.configured_format_of(option)?;
+                Some(Box::new(mapper) as Box<dyn CaseMapper>)
+            })
             .collect()
     }
 
+    /// Same as `format_of`, with options of this convertor (affixes,
+    /// acronyms...) applied, so it converts cases same as in outputs.
+    fn configured_format_of(&self, option: &str) -> Option<Format> {
+        let (prefix, suffix) = &self.affixes;
+        let format = self.format_of(option)?;
+        Some(
+            format
+                .with_affixes(prefix, suffix)
+                .with_acronyms(&self.acronyms)
+                .with_acronym_style(self.acronym_style)
+                .with_abbreviations(&self.abbreviations)
+                .with_acronyms_merged(self.merge_acronyms),
+        )
+    }
+
     /// each word in input -> one line of result in output.
-    fn one_word_to_line(mappers: &[Formatter], case: &NamingCase) -> String {
+    fn write_one_word_line<W: Write>(
+        &self,
+        w: &mut W,
+        mappers: &[Box<dyn CaseMapper>],
+        case: &NamingCase,
+    ) -> io::Result<()> {
         // keep the origin string as the first word.
-        let mut line = case.to_string();
-        line.push(' ');
+        write!(w, "{} ", case)?;
 
         // append target words behind.
-        line.push_str(
-            &mappers
-                .iter()
-                .map(|f| (f.inner)(case))
-                .collect::<Vec<String>>()
-                .join(" "),
-        );
-        line
+        for (index, f) in mappers.iter().enumerate() {
+            if index > 0 {
+                w.write_all(b" ")?;
+            }
+            let value = f.map(case).unwrap();
+            let marker = self.unchanged_marker(case, &value);
+            write!(w, "{}{}", value, marker)?;
+        }
+        Ok(())
+    }
+
+    /// "=" if user enters `--mark-unchanged` option and the converted
+    /// string is same as the origin string, empty otherwise.
+    fn unchanged_marker(&self, case: &NamingCase, value: &str) -> &'static str {
+        if self.mark_unchanged && case.to_string() == value {
+            "="
+        } else {
+            ""
+        }
     }
 
     /// Output in this format when user enters `--json` option,
@@ -154,207 +772,1573 @@ impl Convertor {
     /// Output looks like:
     /// {"result":[{"origin":\<capture1\>,\<first target format\>:\<converted string\>,...},...]}
     pub fn into_json(self) -> String {
-        let mappers = self.select_mappers_base_on_options(&super::JSON_MAPPERS);
+        let mappers = &self.mappers;
+        let keys = self.json_keys();
 
+        let rejected = self.rejected_json_field();
         let mut result = String::from(r#"{"result":["#);
 
         // string "{...},{...},..." for put into json array
         let json_array_fields = self
             .cases
-            .into_iter()
-            .map(|case| Convertor::one_word_to_json(&mappers, &case))
+            .iter()
+            .map(|case| self.one_word_to_json(mappers, &keys, case))
             .collect::<Vec<String>>()
             .join(",");
 
         result.push_str(&json_array_fields);
-        result.push_str("]}");
+        result.push(']');
+        result.push_str(&rejected);
+        result.push('}');
         // "{"result":[{...},{...},...]}"
         result
     }
 
-    fn one_word_to_json(mappers: &[Formatter], case: &NamingCase) -> String {
-        let mut line = r#"{"origin":""#.to_string() + &case.to_string() + "\",";
-
-        line.push_str(
-            &mappers
-                .iter()
-                .map(|f| (f.inner)(case))
-                .collect::<Vec<String>>()
-                .join(","),
-        );
+    /// The conversions as data for embedders, the structured analog of
+    /// `into_json` without serialization: each origin string (in input
+    /// order) paired with its converted strings keyed by target format.
+    ///
+    /// A map has no order, look converted strings up by the kinds of
+    /// `options` (e.g. `NamingCaseKind::Snake` for "s") to keep theirs.
+    /// Options that aren't built-in formats (e.g. custom specs,
+    /// formats only in the registry) have no kind, they are skipped.
+    pub fn into_map(self) -> Vec<(String, HashMap<NamingCaseKind, String>)> {
+        let mappers = &self.mappers;
+        // same order as mappers, which skip unknown options.
+        let kinds: Vec<Option<NamingCaseKind>> = self
+            .options
+            .iter()
+            .filter(|option| self.format_of(option).is_some())
+            .map(|option| option.parse().ok())
+            .collect();
 
-        line.push('}');
-        // "{"origin":"a_a","camel":"aA",...}"
-        line
+        self.cases
+            .iter()
+            .map(|case| {
+                let converted = kinds
+                    .iter()
+                    .zip(mappers.iter())
+                    .filter_map(|(kind, f)| {
+                        Some(((*kind)?, f.map(case).unwrap()))
+                    })
+                    .collect();
+                (case.to_string(), converted)
+            })
+            .collect()
     }
 
-    /// Output in this format when user enters `--regex` option,
-    /// each line represent a captures in input text.
+    /// Output in this format when user enters `--ndjson` option,
+    /// one object per capture per line, same as elements of the "result"
+    /// array of `into_json`, so each line is a valid json on its own.
     ///
     /// Output looks like:
-    /// \<origin string of capture1\> \<target formats mixed OR regex (e.g. "aA|a_a|a-a")\>
-    /// \<origin string of capture2\> \<target formats mixed OR regex\>
-    /// ...
-    pub fn into_regex(self) -> String {
-        let mappers =
-            self.select_mappers_base_on_options(&super::DIRECT_MAPPERS);
+    /// {"origin":\<capture1\>,\<first target format\>:\<converted string\>,...}
+    /// {"origin":\<capture2\>,...}
+    pub fn into_ndjson(self) -> String {
+        let mut buffer = Vec::new();
+        // writing into a Vec<u8> never fails.
+        self.write_ndjson(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
 
-        self.cases
-            .into_iter()
-            .map(|case| Convertor::one_word_to_regex(&mappers, &case))
-            .collect::<Vec<String>>()
-            .join("\n")
+    /// Same as `into_ndjson`, but streams lines to the writer one by one,
+    /// see `write_lines`.
+    pub fn write_ndjson<W: Write>(self, w: &mut W) -> io::Result<()> {
+        let mappers = &self.mappers;
+        let keys = self.json_keys();
+
+        for (index, case) in self.cases.iter().enumerate() {
+            if index > 0 {
+                w.write_all(b"\n")?;
+            }
+            w.write_all(
+                self.one_word_to_json(mappers, &keys, case).as_bytes(),
+            )?;
+        }
+        Ok(())
     }
 
-    fn one_word_to_regex(mappers: &[Formatter], case: &NamingCase) -> String {
-        // keep the origin string as the first word.
-        let mut line = case.to_string();
-        line.push(' ');
+    fn one_word_to_json(
+        &self,
+        mappers: &[Box<dyn CaseMapper>],
+        keys: &[String],
+        case: &NamingCase,
+    ) -> String {
+        let origin = case.to_string();
+        let mut line =
+            r#"{"origin":"#.to_string() + &super::to_json_string(&origin) + ",";
 
-        // join target formats into one regex string with "|"
+        let values: Vec<String> =
+            mappers.iter().map(|f| f.map(case).unwrap()).collect();
         line.push_str(
-            &mappers
+            &keys
                 .iter()
-                .map(|f| (f.inner)(case))
+                .zip(values.iter())
+                .map(|(key, value)| {
+                    super::to_json_string(key)
+                        + ":"
+                        + &super::to_json_string(value)
+                })
                 .collect::<Vec<String>>()
-                .join("|"),
+                .join(","),
         );
+
+        // "changed":{"snake":true,"camel":false,...}
+        if self.mark_unchanged {
+            let changed = keys
+                .iter()
+                .zip(values.iter())
+                .map(|(key, value)| {
+                    format!(
+                        "{}:{}",
+                        super::to_json_string(key),
+                        *value != origin
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(",");
+            line.push_str(&format!(",\"changed\":{{{}}}", changed));
+        }
+
+        line.push('}');
+        // "{"origin":"a_a","camel":"aA",...}"
         line
     }
 
-    /// Output in this format when user enters both `--regex` and `-json` options,
-    /// each array element in "result" field represent a captures in input text.
+    /// Output in this format when user enters `--yaml` option,
+    /// each element of the "result" list represent a captures in input text.
     ///
     /// Output looks like:
-    /// {"result":[{"origin":\<capture1\>,"regex":\<mixed regex string\>},{...},...]}
-    pub fn into_regex_json(self) -> String {
-        let mappers =
-            self.select_mappers_base_on_options(&super::DIRECT_MAPPERS);
-
-        let mut result = String::from(r#"{"result":["#);
+    /// result:
+    ///   - origin: "\<capture1\>"
+    ///     \<first target format\>: "\<converted string\>"
+    ///     ...
+    pub fn into_yaml(self) -> String {
+        if self.cases.is_empty() {
+            return "result: []".to_string();
+        }
+        let mappers = &self.mappers;
 
-        // string "{...},{...},..." for put into json array
-        let json_array_fields = self
+        let list_items = self
             .cases
-            .into_iter()
-            .map(|case| Convertor::one_word_to_regex_json(&mappers, &case))
+            .iter()
+            .map(|case| self.one_word_to_yaml(mappers, case))
             .collect::<Vec<String>>()
-            .join(",");
+            .join("\n");
 
-        result.push_str(&json_array_fields);
-        result.push_str("]}");
-        // "{"result":[{...},{...},...]}"
-        result
+        "result:\n".to_string() + &list_items
     }
 
-    fn one_word_to_regex_json(
-        mappers: &[Formatter],
+    fn one_word_to_yaml(
+        &self,
+        mappers: &[Box<dyn CaseMapper>],
         case: &NamingCase,
     ) -> String {
-        let mut line =
-            r#"{"origin":""#.to_string() + &case.to_string() + r#"","regex":""#;
+        let origin = case.to_string();
+        let mut item =
+            "  - origin: ".to_string() + &super::to_yaml_string(&origin);
+
+        let values: Vec<String> =
+            mappers.iter().map(|f| f.map(case).unwrap()).collect();
+        mappers.iter().zip(values.iter()).for_each(|(f, value)| {
+            item.push_str("\n    ");
+            item.push_str(f.key());
+            item.push_str(": ");
+            item.push_str(&super::to_yaml_string(value));
+        });
+
+        // same as the "changed" object of `into_json`.
+        if self.mark_unchanged {
+            item.push_str("\n    changed:");
+            mappers.iter().zip(values.iter()).for_each(|(f, value)| {
+                item.push_str(&format!(
+                    "\n      {}: {}",
+                    f.key(),
+                    *value != origin
+                ));
+            });
+        }
+        // "  - origin: "a_a"\n    camel: "aA"..."
+        item
+    }
 
-        // concat target formats into an OR regex
-        line.push_str(
-            &mappers
+    /// Output in this format when user enters `--csv` or `--tsv` option,
+    /// a header row (unless turned off by `with_header`) followed by
+    /// one row per capture in input text,
+    /// fields are separated by given delimiter.
+    ///
+    /// Output looks like:
+    /// origin,\<first target format\>,\<second format\>,...
+    /// \<origin string of capture1\>,\<converted string\>,...
+    /// ...
+    pub fn into_csv(self, delimiter: char) -> String {
+        let mappers = &self.mappers;
+
+        let mut rows = Vec::new();
+        if self.header {
+            rows.push(self.csv_header(delimiter));
+        }
+
+        rows.extend(
+            self.cases
                 .iter()
-                .map(|f| (f.inner)(case))
-                .collect::<Vec<String>>()
-                .join("|"),
+                .map(|case| self.one_word_to_csv(mappers, case, delimiter)),
         );
+        rows.join("\n")
+    }
 
-        line.push_str("\"}");
-        // "{"origin":"a_a","regex":"aA|a_a|AA"}"
-        line
+    fn csv_header(&self, delimiter: char) -> String {
+        let mut header = vec!["origin".to_string()];
+        header.extend(self.mappers.iter().map(|f| f.key().to_string()));
+        header.join(&delimiter.to_string())
     }
-}
 
-#[cfg(test)]
-mod filter_tests {
-    use naming_lib::NamingCase;
+    fn one_word_to_csv(
+        &self,
+        mappers: &[Box<dyn CaseMapper>],
+        case: &NamingCase,
+        delimiter: char,
+    ) -> String {
+        let mut fields = vec![case.to_string()];
+        fields.extend(mappers.iter().map(|f| {
+            let value = f.map(case).unwrap();
+            let marker = self.unchanged_marker(case, &value);
+            value + marker
+        }));
+
+        fields
+            .iter()
+            .map(|field| Convertor::escape_csv_field(field, delimiter))
+            .collect::<Vec<String>>()
+            .join(&delimiter.to_string())
+    }
 
-    use crate::to_string_vec;
+    /// Per RFC 4180, fields that contain delimiter, double quotes or
+    /// line breaks are enclosed in double quotes,
+    /// and double quotes inside are escaped by doubling them.
+    fn escape_csv_field(field: &str, delimiter: char) -> String {
+        let need_quotes = field
+            .chars()
+            .any(|c| c == delimiter || c == '"' || c == '\n' || c == '\r');
+        if need_quotes {
+            "\"".to_string() + &field.replace('"', "\"\"") + "\""
+        } else {
+            field.to_string()
+        }
+    }
 
-    use super::Filter;
+    /// Output in this format when user enters `--table` option,
+    /// same as `into_csv`, but columns are padded with spaces to be aligned,
+    /// widths are measured by display width (e.g. CJK characters take 2).
+    ///
+    /// Output looks like:
+    /// origin     snake       kebab
+    /// camelCase  camel_case  camel-case
+    /// ...
+    pub fn into_table(self) -> String {
+        let mappers = &self.mappers;
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        if self.header {
+            rows.push(
+                std::iter::once("origin")
+                    .chain(mappers.iter().map(|f| f.key()))
+                    .map(|name| name.to_string())
+                    .collect(),
+            );
+        }
+        rows.extend(self.cases.iter().map(|case| {
+            let mut row = vec![case.to_string()];
+            row.extend(mappers.iter().map(|f| {
+                let value = f.map(case).unwrap();
+                let marker = self.unchanged_marker(case, &value);
+                value + marker
+            }));
+            row
+        }));
+
+        let mut widths = vec![0; mappers.len() + 1];
+        rows.iter().for_each(|row| {
+            row.iter().enumerate().for_each(|(i, cell)| {
+                widths[i] = widths[i].max(UnicodeWidthStr::width(cell.as_str()))
+            })
+        });
 
-    #[test]
-    fn find_hungarian_camel_conflict() {
-        assert!(Filter::has_hungarian_camel_conflict(&[
-            "c".to_string(),
-            "h".to_string()
-        ]));
+        rows.iter()
+            .map(|row| Convertor::one_row_to_table(row, &widths))
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 
-    #[test]
-    fn filter_words_with_option() {
-        let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
-        let filter = Filter::new(Some(options)).unwrap();
-        let words: Vec<String> = vec![
-            "SCREAMING_SNAKE",
-            "snake_case",
-            "kebab-case",
-            "camelCase",
-            "PascalCase",
-            "-invalid_",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect();
+    fn one_row_to_table(row: &[String], widths: &[usize]) -> String {
+        let mut line = String::new();
+        for (i, cell) in row.iter().enumerate() {
+            line.push_str(cell);
+            // no trailing spaces after the last column.
+            if i + 1 < row.len() {
+                let padding = widths[i] - UnicodeWidthStr::width(cell.as_str());
+                line.push_str(&" ".repeat(padding + 2));
+            }
+        }
+        line
+    }
 
-        let mut expect = words.clone();
-        // remove the invalid word at tail
+    /// Output in this format when user enters `--count` option,
+    /// count captures by their source naming case, instead of converting them.
+    /// Lines are sorted by count (descending), then by case name.
+    ///
+    /// Output looks like:
+    /// camel: 42
+    /// snake: 17
+    /// ...
+    pub fn into_counts(self) -> String {
+        self.count_cases()
+            .iter()
+            .map(|(name, count)| format!("{}: {}", name, count))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Output in this format when user enters both `--count` and `--json` options.
+    ///
+    /// Output looks like:
+    /// {"counts":{"camel":42,"snake":17,...}}
+    pub fn into_counts_json(self) -> String {
+        let fields = self
+            .count_cases()
+            .iter()
+            .map(|(name, count)| format!("\"{}\":{}", name, count))
+            .collect::<Vec<String>>()
+            .join(",");
+        format!("{{\"counts\":{{{}}}}}", fields)
+    }
+
+    /// Output in this format when user enters `--group-by-case` option,
+    /// same lines as `into_lines`, but grouped under a heading of their
+    /// source naming case. Groups are in the order of their first capture,
+    /// and separated by blank lines.
+    ///
+    /// Output looks like:
+    /// == camel ==
+    /// \<origin string of capture1\> \<converted strings\>
+    /// \<origin string of capture3\> \<converted strings\>
+    ///
+    /// == snake ==
+    /// \<origin string of capture2\> \<converted strings\>
+    pub fn into_groups(self) -> String {
+        let mappers = &self.mappers;
+
+        self.group_cases()
+            .iter()
+            .map(|(name, cases)| {
+                let mut buffer = format!("== {} ==", name).into_bytes();
+                for case in cases {
+                    buffer.push(b'\n');
+                    // writing into a Vec<u8> never fails.
+                    self.write_one_word_line(&mut buffer, mappers, case)
+                        .unwrap();
+                }
+                String::from_utf8(buffer).unwrap()
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+
+    /// Output in this format when user enters both `--group-by-case`
+    /// and `--json` options, objects are same as `into_json`'s.
+    ///
+    /// Output looks like:
+    /// {"camel":[{"origin":\<capture1\>,...},...],"snake":[...],...}
+    pub fn into_groups_json(self) -> String {
+        let mappers = &self.mappers;
+        let keys = self.json_keys();
+
+        let groups = self
+            .group_cases()
+            .iter()
+            .map(|(name, cases)| {
+                let objects = cases
+                    .iter()
+                    .map(|case| self.one_word_to_json(mappers, &keys, case))
+                    .collect::<Vec<String>>()
+                    .join(",");
+                format!("\"{}\":[{}]", name, objects)
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        // without groups, there is no field before the rejected one.
+        let fields = groups + &self.rejected_json_field();
+        format!("{{{}}}", fields.trim_start_matches(','))
+    }
+
+    /// Partition captures by their source naming case (see `count_cases`),
+    /// groups and captures inside them keep the input order.
+    fn group_cases(&self) -> Vec<(&'static str, Vec<&NamingCase>)> {
+        let mut groups: Vec<(&'static str, Vec<&NamingCase>)> = Vec::new();
+        for case in &self.cases {
+            let name = case.case_name();
+            match groups.iter_mut().find(|(group, _)| *group == name) {
+                Some((_, cases)) => cases.push(case),
+                None => groups.push((name, vec![case])),
+            }
+        }
+        groups
+    }
+
+    /// Output in this format when user enters `--words` option,
+    /// the lowercased words of each capture, one per line,
+    /// captures are separated by blank lines.
+    ///
+    /// Output looks like:
+    /// get
+    /// user
+    /// name
+    ///
+    /// page
+    /// ...
+    pub fn into_words(self) -> String {
+        self.cases
+            .iter()
+            .map(|case| case.words().join("\n"))
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+
+    /// Output in this format when user enters `--diff` option,
+    /// only list captures whose target case differs from the origin.
+    ///
+    /// Output looks like:
+    /// pageSize -> page_size
+    /// ...
+    pub fn into_diff(self, target: NamingCaseKind) -> String {
+        self.diff(target)
+            .iter()
+            .map(|(case, converted)| format!("{} -> {}", case, converted))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Output in this format when user enters both `--diff` and `--json`
+    /// options, "from" is the current case of the origin string.
+    ///
+    /// Output looks like:
+    /// {"result":[{"origin":"pageSize","from":"camel","to":"page_size"},...]}
+    pub fn into_diff_json(self, target: NamingCaseKind) -> String {
+        let json_array_fields = self
+            .diff(target)
+            .iter()
+            .map(|(case, converted)| {
+                format!(
+                    r#"{{"origin":{},"from":{},"to":{}}}"#,
+                    super::to_json_string(&case.to_string()),
+                    super::to_json_string(case.case_name()),
+                    super::to_json_string(converted)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        format!(
+            r#"{{"result":[{}]{}}}"#,
+            json_array_fields,
+            self.rejected_json_field()
+        )
+    }
+
+    // captures with their converted strings, which differ from origins.
+    fn diff(&self, target: NamingCaseKind) -> Vec<(&NamingCase, String)> {
+        self.cases
+            .iter()
+            .filter_map(|case| {
+                let converted = target.convert(case, &self.acronyms).ok()?;
+                if converted == case.to_string() {
+                    None
+                } else {
+                    Some((case, converted))
+                }
+            })
+            .collect()
+    }
+
+    /// Output in this format when user enters `--stats` option,
+    /// a summary of the whole run, with the size of given input.
+    ///
+    /// Output looks like:
+    /// {"stats":{"files":2,"bytes":1024,"captures":59,
+    /// "counts":{"camel":42,"snake":17,...}}}
+    pub fn into_stats(self, input: &InputStats) -> String {
+        let counts = self
+            .count_cases()
+            .iter()
+            .map(|(name, count)| format!("\"{}\":{}", name, count))
+            .collect::<Vec<String>>()
+            .join(",");
+        format!(
+            "{{\"stats\":{{\"files\":{},\"bytes\":{},\"captures\":{},\"counts\":{{{}}}}}}}",
+            input.files,
+            input.bytes,
+            self.cases.len(),
+            counts
+        )
+    }
+
+    fn count_cases(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: HashMap<&'static str, usize> = HashMap::new();
+        self.cases
+            .iter()
+            .for_each(|case| *counts.entry(case.case_name()).or_insert(0) += 1);
+
+        let mut counts: Vec<(&'static str, usize)> =
+            counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+    }
+
+    /// Answer user's `--only-ambiguous` option,
+    /// keep only cases whose origin string is valid in more than one format,
+    /// e.g. "abc" is a single word, a snake case and a kebab case word.
+    pub fn retain_ambiguous(mut self) -> Convertor {
+        self.cases
+            .retain(|case| super::which_cases(&case.to_string()).len() > 1);
+        self
+    }
+
+    /// Answer user's `--limit` option, keep only the first `limit` cases,
+    /// all output formats are then built from the truncated cases.
+    pub fn limit(mut self, limit: usize) -> Convertor {
+        self.cases.truncate(limit);
+        self
+    }
+
+    /// Answer user's `--sample` option, keep `size` randomly selected cases
+    /// in their origin order, the same `seed` always selects the same cases.
+    #[cfg(feature = "sample")]
+    pub fn sample(mut self, size: usize, seed: Option<u64>) -> Convertor {
+        let len = self.cases.len();
+        if size >= len {
+            return self;
+        }
+        let rng = match seed {
+            Some(seed) => fastrand::Rng::with_seed(seed),
+            None => fastrand::Rng::new(),
+        };
+        // partial Fisher-Yates shuffle, the first `size` indices are selected.
+        let mut indices: Vec<usize> = (0..len).collect();
+        for i in 0..size {
+            indices.swap(i, rng.usize(i..len));
+        }
+        let mut selected = vec![false; len];
+        for &index in &indices[..size] {
+            selected[index] = true;
+        }
+        let mut selected = selected.into_iter();
+        self.cases.retain(|_| selected.next().unwrap());
+        self
+    }
+
+    /// Answer user's `--already` option, keep only cases whose origin
+    /// string isn't in the target format yet, i.e. the ones need renaming.
+    pub fn retain_not_already(mut self, target: NamingCaseKind) -> Convertor {
+        self.cases.retain(|case| !super::is_case(&case.to_string(), target));
+        self
+    }
+
+    /// Output in this format when user enters `--only-ambiguous` option,
+    /// list every naming case that each capture could be recognized as,
+    /// the first one is the case this tool actually uses.
+    ///
+    /// Output looks like:
+    /// \<origin string of capture1\> \<first candidate\> \<second candidate\> ...
+    /// ...
+    pub fn into_candidates(self) -> String {
+        self.cases
+            .iter()
+            .map(|case| {
+                let mut line = vec![case.to_string()];
+                line.extend(
+                    Convertor::candidate_names(case)
+                        .into_iter()
+                        .map(|name| name.to_string()),
+                );
+                line.join(" ")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Output in this format when user enters both
+    /// `--only-ambiguous` and `--json` options.
+    ///
+    /// Output looks like:
+    /// {"result":[{"origin":\<capture1\>,"candidates":["single_word","snake",...]},...]}
+    pub fn into_candidates_json(self) -> String {
+        let json_array_fields = self
+            .cases
+            .iter()
+            .map(|case| {
+                let names: Vec<String> = Convertor::candidate_names(case)
+                    .into_iter()
+                    .map(|name| name.to_string())
+                    .collect();
+                format!(
+                    r#"{{"origin":{},"candidates":{}}}"#,
+                    super::to_json_string(&case.to_string()),
+                    Convertor::to_json_array(&names, ",")
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        format!(r#"{{"result":[{}]}}"#, json_array_fields)
+    }
+
+    /// Output in this format when user enters `--explain` option,
+    /// explain how each capture is classified, see `explain`.
+    ///
+    /// Output looks like:
+    /// \<origin string of capture1\>
+    ///   case: camel
+    ///   words: page, size
+    ///   also valid: -
+    ///   not snake: has uppercase letters
+    ///   ...
+    pub fn into_explanations(self) -> String {
+        self.cases
+            .iter()
+            .map(|case| super::explain(&case.to_string()).to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn candidate_names(case: &NamingCase) -> Vec<&'static str> {
+        super::which_cases(&case.to_string())
+            .iter()
+            .map(|candidate| candidate.case_name())
+            .collect()
+    }
+
+    /// Output in this format when user enters `--regex` option,
+    /// each line represent a captures in input text.
+    ///
+    /// Output looks like:
+    /// \<origin string of capture1\> \<target formats mixed OR regex (e.g. "aA|a_a|a-a")\>
+    /// \<origin string of capture2\> \<target formats mixed OR regex\>
+    /// ...
+    pub fn into_regex(self) -> String {
+        let mappers = &self.mappers;
+
+        self.cases
+            .iter()
+            .map(|case| {
+                // keep the origin string as the first word.
+                case.to_string() + " " + &self.one_word_to_regex(mappers, case)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Join target formats into one regex string with "|",
+    /// then wrap it with the bounds set by `with_regex_bounds`,
+    /// and the case-insensitive flag set by `with_regex_icase`.
+    fn one_word_to_regex(
+        &self,
+        mappers: &[Box<dyn CaseMapper>],
+        case: &NamingCase,
+    ) -> String {
+        let mut alternatives: Vec<String> = mappers
+            .iter()
+            .map(|f| escape_regex(&f.map(case).unwrap()))
+            .collect();
+        if self.regex_icase {
+            let mut folded = HashSet::new();
+            alternatives.retain(|alt| folded.insert(alt.to_lowercase()));
+        }
+
+        let flag = if self.regex_icase { "(?i)" } else { "" };
+        let (prefix, suffix) = &self.regex_bounds;
+        let width = match self.regex_wrap {
+            Some(width) => width,
+            None => {
+                return flag.to_string()
+                    + prefix
+                    + &alternatives.join("|")
+                    + suffix
+            }
+        };
+
+        // whitespace is ignored in extended mode, so literal one is escaped,
+        // "#" (starts a comment in this mode) is already escaped.
+        let alternatives: Vec<String> = alternatives
+            .iter()
+            .map(|alt| escape_regex_whitespace(alt))
+            .collect();
+        let mut lines = vec!["(?x)".to_string() + flag + prefix];
+        lines.extend(wrap_alternatives(&alternatives, width));
+        if !suffix.is_empty() {
+            lines.push(suffix.to_string());
+        }
+        lines.join("\n")
+    }
+
+    /// Output in this format when user enters both `--regex` and `-json` options,
+    /// each array element in "result" field represent a captures in input text.
+    ///
+    /// Output looks like:
+    /// {"result":[{"origin":\<capture1\>,"regex":\<mixed regex string\>},{...},...]}
+    pub fn into_regex_json(self) -> String {
+        let mappers = &self.mappers;
+
+        let rejected = self.rejected_json_field();
+        let mut result = String::from(r#"{"result":["#);
+
+        // string "{...},{...},..." for put into json array
+        let json_array_fields = self
+            .cases
+            .iter()
+            .map(|case| self.one_word_to_regex_json(mappers, case))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        result.push_str(&json_array_fields);
+        result.push(']');
+        result.push_str(&rejected);
+        result.push('}');
+        // "{"result":[{...},{...},...]}"
+        result
+    }
+
+    fn one_word_to_regex_json(
+        &self,
+        mappers: &[Box<dyn CaseMapper>],
+        case: &NamingCase,
+    ) -> String {
+        let mut line = r#"{"origin":"#.to_string()
+            + &super::to_json_string(&case.to_string())
+            + r#","regex":"#;
+
+        // backslashes in regex (e.g. "\b") are escaped in json string.
+        line.push_str(&super::to_json_string(
+            &self.one_word_to_regex(mappers, case),
+        ));
+
+        line.push('}');
+        // "{"origin":"a_a","regex":"aA|a_a|AA"}"
+        line
+    }
+
+    /// Same as `into_json`, but the output is indented with two spaces:
+    /// {
+    ///   "result": [
+    ///     {
+    ///       "origin": "\<capture1\>",
+    ///       "\<first target format\>": "\<converted string\>",
+    ///       ...
+    ///     },
+    ///     ...
+    ///   ]
+    /// }
+    pub fn into_pretty_json(self) -> String {
+        let mappers = &self.mappers;
+        let keys = self.json_keys();
+
+        let objects = self
+            .cases
+            .iter()
+            .map(|case| {
+                let origin = case.to_string();
+                let values: Vec<String> =
+                    mappers.iter().map(|f| f.map(case).unwrap()).collect();
+                let mut fields =
+                    vec![("origin", super::to_json_string(&origin))];
+                fields.extend(keys.iter().zip(values.iter()).map(
+                    |(key, value)| (key.as_str(), super::to_json_string(value)),
+                ));
+
+                // same as the "changed" object of `into_json`.
+                if self.mark_unchanged {
+                    let changed = keys
+                        .iter()
+                        .zip(values.iter())
+                        .map(|(key, value)| {
+                            format!(
+                                "        {}: {}",
+                                super::to_json_string(key),
+                                *value != origin
+                            )
+                        })
+                        .collect::<Vec<String>>()
+                        .join(",\n");
+                    fields.push((
+                        "changed",
+                        format!("{{\n{}\n      }}", changed),
+                    ));
+                }
+                fields
+            })
+            .collect();
+        Convertor::to_pretty_json_result(objects, &self.rejected)
+    }
+
+    /// Same as `into_regex_json`, but the output is indented with two spaces.
+    pub fn into_pretty_regex_json(self) -> String {
+        let mappers = &self.mappers;
+
+        let objects = self
+            .cases
+            .iter()
+            .map(|case| {
+                let regex = self.one_word_to_regex(mappers, case);
+                vec![
+                    ("origin", super::to_json_string(&case.to_string())),
+                    ("regex", super::to_json_string(&regex)),
+                ]
+            })
+            .collect();
+        Convertor::to_pretty_json_result(objects, &self.rejected)
+    }
+
+    // values of fields are serialized json, e.g. "\"a_a\"" or "{...}".
+    fn to_pretty_json_result(
+        objects: Vec<Vec<(&str, String)>>,
+        rejected: &Option<Vec<String>>,
+    ) -> String {
+        let rejected = match rejected {
+            Some(words) => format!(
+                ",\n  \"rejected\": {}",
+                Convertor::to_json_array(words, ", ")
+            ),
+            None => String::new(),
+        };
+        if objects.is_empty() {
+            return format!("{{\n  \"result\": []{}\n}}", rejected);
+        }
+
+        let objects = objects
+            .iter()
+            .map(|fields| {
+                let fields = fields
+                    .iter()
+                    .map(|(key, value)| {
+                        format!(
+                            "      {}: {}",
+                            super::to_json_string(key),
+                            value
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",\n");
+                format!("    {{\n{}\n    }}", fields)
+            })
+            .collect::<Vec<String>>()
+            .join(",\n");
+
+        format!("{{\n  \"result\": [\n{}\n  ]{}\n}}", objects, rejected)
+    }
+
+    /// Field appended to json outputs when `with_rejected` is set,
+    /// looks like: ,"rejected":["-invalid_",...]
+    fn rejected_json_field(&self) -> String {
+        match &self.rejected {
+            Some(words) => {
+                r#","rejected":"#.to_string()
+                    + &Convertor::to_json_array(words, ",")
+            }
+            None => String::new(),
+        }
+    }
+
+    fn to_json_array(words: &[String], separator: &str) -> String {
+        let elements = words
+            .iter()
+            .map(|word| super::to_json_string(word))
+            .collect::<Vec<String>>()
+            .join(separator);
+        format!("[{}]", elements)
+    }
+}
+
+/// Wrap text with ANSI SGR escape codes of given color.
+fn paint(text: &str, color: &str) -> String {
+    format!("\x1b[{}m{}\x1b[0m", color, text)
+}
+
+// ANSI color codes of each format, ambiguous single words are bold,
+// and invalid words are dimmed.
+fn case_color(case: &NamingCase) -> &'static str {
+    match case {
+        NamingCase::ScreamingSnake(_) => {
+            format_color(&NamingCaseKind::ScreamingSnake)
+        }
+        NamingCase::Snake(_) => format_color(&NamingCaseKind::Snake),
+        NamingCase::Kebab(_) => format_color(&NamingCaseKind::Kebab),
+        NamingCase::Camel(_) => format_color(&NamingCaseKind::Camel),
+        NamingCase::Pascal(_) => format_color(&NamingCaseKind::Pascal),
+        NamingCase::SingleWord(_) => "1",
+        NamingCase::Invalid(_) => "2",
+    }
+}
+
+fn format_color(kind: &NamingCaseKind) -> &'static str {
+    match kind {
+        NamingCaseKind::ScreamingSnake => "31",
+        NamingCaseKind::Snake => "32",
+        NamingCaseKind::Kebab => "33",
+        NamingCaseKind::Camel => "34",
+        NamingCaseKind::Pascal => "35",
+        NamingCaseKind::Train => "36",
+        NamingCaseKind::Dot => "91",
+        NamingCaseKind::Flat => "92",
+        NamingCaseKind::UpperFlat => "93",
+        NamingCaseKind::Ada => "94",
+        NamingCaseKind::Cobol => "95",
+        NamingCaseKind::Path => "96",
+    }
+}
+
+/// Escape regex meta characters, so that converted strings
+/// (e.g. "a.b" in dot case) are matched literally.
+fn escape_regex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$#".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape whitespace in a regex that is going to be used in extended mode,
+/// e.g. the ones come from `--prefix` and `--suffix` options.
+fn escape_regex_whitespace(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            ' ' => escaped.push_str(r"\ "),
+            '\t' => escaped.push_str(r"\t"),
+            '\n' => escaped.push_str(r"\n"),
+            '\r' => escaped.push_str(r"\r"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Pack "|"-joined alternatives into lines of at most `width` columns
+/// (counting the two spaces of indentation and the trailing "|").
+fn wrap_alternatives(alternatives: &[String], width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for (i, alt) in alternatives.iter().enumerate() {
+        let last = i + 1 == alternatives.len();
+        let trailing = if last { 0 } else { 1 };
+        if line.is_empty() {
+            line = "  ".to_string() + alt;
+            continue;
+        }
+        let joined = UnicodeWidthStr::width(line.as_str())
+            + 1
+            + UnicodeWidthStr::width(alt.as_str())
+            + trailing;
+        if joined <= width {
+            line.push('|');
+            line.push_str(alt);
+        } else {
+            line.push('|');
+            lines.push(line);
+            line = "  ".to_string() + alt;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Output plain words (e.g. the ones found by `--invalid-only` option)
+/// as a json object, looks like: {"result":["-invalid_",...]}
+pub fn words_to_json(words: &[String]) -> String {
+    format!(r#"{{"result":{}}}"#, Convertor::to_json_array(words, ","))
+}
+
+fn to_acronym_set(acronyms: Option<Vec<String>>) -> HashSet<String> {
+    acronyms
+        .unwrap_or_default()
+        .iter()
+        .map(|acronym| acronym.to_uppercase())
+        .collect()
+}
+
+/// Answer user's `--json-keys` option, how format names are spelled
+/// as keys of json outputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonKeys {
+    /// Names of formats, e.g. "screaming_snake".
+    #[default]
+    Snake,
+    /// Names of formats in camel case, e.g. "screamingSnake".
+    Camel,
+    /// Values of `--output` option, e.g. "S".
+    Short,
+}
+
+impl FromStr for JsonKeys {
+    type Err = NamingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "snake" => Ok(JsonKeys::Snake),
+            "camel" => Ok(JsonKeys::Camel),
+            "short" => Ok(JsonKeys::Short),
+            _ => Err(NamingError::InvalidOptionValue {
+                option: "json-keys".to_string(),
+                value: s.to_string(),
+                expected: "one of camel, snake and short".to_string(),
+            }),
+        }
+    }
+}
+
+/// Answer user's `--replace` option,
+/// rewrite captured words in input text to the target naming case,
+/// while keeping the rest of the text untouched.
+pub struct Replacer {
+    target: Format,
+    acronyms: HashSet<String>,
+}
+
+impl Replacer {
+    /// Target is one of the `--output` option's values,
+    /// a format letter, name, or a "custom:..." spec (see `CaseSpec`).
+    pub fn new(target: &str) -> Result<Replacer, NamingError> {
+        let target = if target.starts_with("custom:") {
+            Format::custom(target, target.parse()?)
+        } else {
+            let kind: NamingCaseKind = target.parse()?;
+            ConversionRegistry::default()
+                .get(kind.letter())
+                .cloned()
+                .ok_or_else(|| NamingError::UnknownCase(target.to_string()))?
+        };
+        Ok(Replacer { target, acronyms: HashSet::new() })
+    }
+
+    /// Same as `Convertor::with_acronyms`.
+    pub fn with_acronyms(mut self, acronyms: Option<Vec<String>>) -> Replacer {
+        self.acronyms = to_acronym_set(acronyms);
+        self
+    }
+
+    /// Replace words captured by captor and accepted by filter
+    /// with their target naming case.
+    /// A prefix stripped by filter (see `Filter::with_stripped_prefix`)
+    /// is put back, e.g. "m_fooBar" -> "m_foo_bar" in snake case.
+    pub fn replace(
+        &self,
+        captor: &Captor,
+        filter: &Filter,
+        text: &str,
+    ) -> String {
+        captor.replace_words(text, |word| {
+            let case = filter.to_naming_case(word)?;
+            let converted = self.target.convert(&case, &self.acronyms).ok()?;
+            Some(filter.stripped_prefix_of(word).to_string() + &converted)
+        })
+    }
+}
+
+#[cfg(test)]
+mod filter_tests {
+    use naming_lib::NamingCase;
+
+    use crate::{to_string_vec, NamingError};
+
+    use super::Filter;
+
+    #[test]
+    fn find_hungarian_camel_conflict() {
+        assert!(Filter::has_hungarian_camel_conflict(&[
+            "c".to_string(),
+            "h".to_string()
+        ]));
+    }
+
+    #[test]
+    fn unknown_option_is_an_error() {
+        let err =
+            Filter::new(Some(to_string_vec(vec!["s", "x"]))).err().unwrap();
+        assert!(matches!(err, NamingError::InvalidFilterLetter { .. }));
+        assert_eq!(
+            err.to_string(),
+            "naming: In option --filter, unknown format `x`, \
+            valid formats: S,s,k,c,h,p."
+        );
+    }
+
+    #[test]
+    fn empty_options_match_nothing() {
+        let words = to_string_vec(vec!["snake_case", "camelCase"]);
+        for options in [vec![], vec!["".to_string()]] {
+            let filter = Filter::new(Some(options)).unwrap();
+            assert!(filter.to_naming_cases_from(words.clone()).is_empty());
+        }
+        let filter = Filter::new(None).unwrap();
+        assert!(filter.to_naming_cases_from(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn filter_words_with_option() {
+        let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
+        let filter = Filter::new(Some(options)).unwrap();
+        let words: Vec<String> = vec![
+            "SCREAMING_SNAKE",
+            "snake_case",
+            "kebab-case",
+            "camelCase",
+            "PascalCase",
+            "-invalid_",
+        ]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let mut expect = words.clone();
+        // remove the invalid word at tail
         expect.pop();
         let actual = Filter::filter_words_with_options(&filter, words);
         assert_eq!(actual, expect);
     }
 
     #[test]
-    fn convert_words_as_hungarian_notation() {
-        let options = vec!["h".to_string()];
-        let words: Vec<String> = vec!["intPageSize".to_string()];
+    fn filter_out_words_with_option() {
+        let filter = Filter::new(None)
+            .unwrap()
+            .excluding(Some(to_string_vec(vec!["s"])));
+        let words = to_string_vec(vec![
+            "snake_case",
+            "abc",
+            "kebab-case",
+            "camelCase",
+            "SCREAMING_SNAKE",
+        ]);
+        let actual = filter.filter_words_with_options(words);
+        // "abc" is also a valid snake case word.
+        let expect =
+            to_string_vec(vec!["kebab-case", "camelCase", "SCREAMING_SNAKE"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn list_rejected_words() {
+        let filter = Filter::new(Some(to_string_vec(vec!["s"]))).unwrap();
+        let words = to_string_vec(vec!["snake_case", "-invalid_", "camelCase"]);
+        assert_eq!(
+            filter.rejected_words(&words),
+            to_string_vec(vec!["-invalid_", "camelCase"])
+        );
+    }
+
+    #[test]
+    fn strip_prefix_before_classifying() {
+        let filter =
+            Filter::new(None).unwrap().with_stripped_prefix(Some("m_"));
+        let words = to_string_vec(vec!["m_fooBar", "g_config", "m_"]);
+        let actual = filter.to_naming_cases_from(words);
+        assert_eq!(
+            actual,
+            vec![
+                NamingCase::Camel("fooBar".to_string()),
+                NamingCase::Snake("g_config".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn tolerate_letters_in_wrong_case() {
+        let words = to_string_vec(vec![
+            "user_Id",
+            "Page_Size",
+            "PageSize",
+            "Max-size",
+            "ns::Type",
+        ]);
+        let filter = |tolerance| {
+            Filter::new(Some(to_string_vec(vec!["s", "c"])))
+                .unwrap()
+                .with_tolerance(tolerance)
+        };
+        assert!(filter(0).filter_words_with_options(words.clone()).is_empty());
+        assert_eq!(
+            filter(1).filter_words_with_options(words.clone()),
+            to_string_vec(vec!["user_id", "pageSize", "ns::type"])
+        );
+        assert_eq!(
+            filter(2).filter_words_with_options(words),
+            to_string_vec(vec!["user_id", "page_size", "pageSize", "ns::type"])
+        );
+    }
+
+    #[test]
+    fn filter_qualified_names_by_segments() {
+        let filter = Filter::new(Some(to_string_vec(vec!["s", "c"]))).unwrap();
+        let words =
+            to_string_vec(vec!["obj.getName", "ns::Type", "a..b", "user_id"]);
+        let actual = filter.to_naming_cases_from(words);
+        // "Type" is neither snake case nor camel case.
+        assert_eq!(
+            actual,
+            vec![
+                NamingCase::Invalid("obj.getName".to_string()),
+                NamingCase::Snake("user_id".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn convert_words_as_hungarian_notation() {
+        let options = vec!["h".to_string()];
+        let words: Vec<String> = vec!["intPageSize".to_string()];
+
+        let actual =
+            Filter::new(Some(options)).unwrap().to_naming_cases_from(words);
+        assert_eq!(actual, vec![NamingCase::Pascal("PageSize".to_string())]);
+    }
+
+    #[test]
+    fn convert_words_with_multi_letter_hungarian_prefixes() {
+        let options = vec!["h".to_string()];
+        let words: Vec<String> =
+            to_string_vec(vec!["lpszUserName", "intCount"]);
+
+        let actual = Filter::new(Some(options))
+            .unwrap()
+            .with_hungarian_prefixes(Some(to_string_vec(vec!["lpsz"])))
+            .to_naming_cases_from(words);
+        assert_eq!(
+            actual,
+            vec![
+                NamingCase::Pascal("UserName".to_string()),
+                naming_lib::from_hungarian_notation("intCount")
+            ]
+        );
+    }
+
+    #[test]
+    fn convert_words_to_naming_cases() {
+        let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
+        let words: Vec<String> = vec![
+            "SCREAMING_SNAKE",
+            "snake_case",
+            "kebab-case",
+            "camelCase",
+            "PascalCase",
+            "-invalid_",
+        ]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let actual =
+            Filter::new(Some(options)).unwrap().to_naming_cases_from(words);
+
+        let expect = vec![
+            NamingCase::ScreamingSnake("SCREAMING_SNAKE".to_string()),
+            NamingCase::Snake("snake_case".to_string()),
+            NamingCase::Kebab("kebab-case".to_string()),
+            NamingCase::Camel("camelCase".to_string()),
+            NamingCase::Pascal("PascalCase".to_string()),
+        ];
+
+        assert_eq!(actual, expect);
+    }
+}
+
+#[cfg(test)]
+mod replacer_tests {
+    use crate::{to_string_vec, Captor, Filter};
+
+    use super::Replacer;
+
+    #[test]
+    fn replace_words_in_text() {
+        let text = "int fooBar = BAZ_QUX + x-y;";
+        let actual = Replacer::new("s").unwrap().replace(
+            &Captor::new(None).unwrap(),
+            &Filter::new(None).unwrap(),
+            text,
+        );
+        assert_eq!(actual, "int foo_bar = baz_qux + x_y;");
+    }
+
+    #[test]
+    fn only_replace_words_that_pass_filter() {
+        let text = "fooBar baz_qux";
+        let actual = Replacer::new("k").unwrap().replace(
+            &Captor::new(None).unwrap(),
+            &Filter::new(Some(to_string_vec(vec!["s"]))).unwrap(),
+            text,
+        );
+        assert_eq!(actual, "fooBar baz-qux");
+    }
+
+    #[test]
+    fn replace_with_custom_format() {
+        let actual = Replacer::new("custom:.:lower").unwrap().replace(
+            &Captor::new(None).unwrap(),
+            &Filter::new(None).unwrap(),
+            "x = fooBar;",
+        );
+        assert_eq!(actual, "x = foo.bar;");
+    }
+
+    #[test]
+    fn unknown_target_is_an_error() {
+        assert!(Replacer::new("x").is_err());
+        assert!(Replacer::new("custom:").is_err());
+    }
+}
+
+#[cfg(test)]
+mod convertor_tests {
+    use naming_lib::{self as naming, NamingCase};
+
+    use crate::{
+        to_string_vec, ConversionRegistry, Filter, InputStats, NamingCaseKind,
+    };
+
+    use super::{escape_regex, words_to_json, Convertor, JsonKeys};
+
+    #[test]
+    fn options_mixing_letters_and_names() {
+        let options = to_string_vec(vec!["screaming-snake", "s", "camel"]);
+        let cases = vec![naming::which_case("pageSize")];
+
+        let convertor = Convertor::new(Some(options), cases);
+        let convertor = convertor.with_json_keys(JsonKeys::Short).validated();
+
+        assert_eq!(
+            convertor.unwrap().into_json(),
+            r#"{"result":[{"origin":"pageSize","S":"PAGE_SIZE","s":"page_size","c":"pageSize"}]}"#
+        );
+    }
+
+    #[test]
+    fn output_grouped_by_case() {
+        let options = to_string_vec(vec!["s", "k"]);
+        let words = ["pageSize", "user_id", "userName"];
+        let cases = || words.iter().map(|w| naming::which_case(w)).collect();
+
+        let actual =
+            Convertor::new(Some(options.clone()), cases()).into_groups();
+        let expect = "== camel ==
+pageSize page_size page-size
+userName user_name user-name
+
+== snake ==
+user_id user_id user-id";
+        assert_eq!(actual, expect);
+
+        let actual = Convertor::new(Some(options), cases())
+            .with_rejected(to_string_vec(vec!["-x"]))
+            .into_groups_json();
+        let expect = concat!(
+            r#"{"camel":[{"origin":"pageSize","snake":"page_size","kebab":"page-size"},"#,
+            r#"{"origin":"userName","snake":"user_name","kebab":"user-name"}],"#,
+            r#""snake":[{"origin":"user_id","snake":"user_id","kebab":"user-id"}],"#,
+            r#""rejected":["-x"]}"#
+        );
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn output_no_groups_as_json() {
+        let convertor = Convertor::new(None, Vec::new());
+        assert_eq!(
+            convertor
+                .with_rejected(to_string_vec(vec!["-x"]))
+                .into_groups_json(),
+            r#"{"rejected":["-x"]}"#
+        );
+    }
+
+    #[test]
+    fn unknown_output_option_is_an_error() {
+        let options = to_string_vec(vec!["s", "x"]);
+        let err = Convertor::new(Some(options), Vec::new())
+            .validated()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "naming: unknown output format `x`, valid: S,s,k,c,p,t,d,f,F,a,C,/."
+        );
+
+        let options = to_string_vec(vec!["s", "x"]);
+        let registry = ConversionRegistry::default()
+            .with_format("x", |case, _| case.to_snake());
+        assert!(Convertor::new(Some(options), Vec::new())
+            .with_registry(registry)
+            .validated()
+            .is_ok());
+    }
+
+    #[test]
+    fn empty_options_default_to_first_5_formats() {
+        let cases = || vec![naming::which_case("pageSize")];
+        let expect = "pageSize PAGE_SIZE page_size page-size pageSize PageSize";
+        assert_eq!(Convertor::new(None, cases()).into_lines(), expect);
+        assert_eq!(Convertor::new(Some(vec![]), cases()).into_lines(), expect);
+    }
+
+    #[test]
+    fn write_lines_same_as_into_lines() {
+        let options = Some(to_string_vec(vec!["S", "c"]));
+        let cases = || {
+            vec![naming::which_case("pageSize"), naming::which_case("user_id")]
+        };
+
+        let mut buffer = Vec::new();
+        Convertor::new(options.clone(), cases())
+            .write_lines(&mut buffer)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            Convertor::new(options, cases()).into_lines()
+        );
+        assert_eq!(
+            Convertor::new(None, cases()).into_lines(),
+            "pageSize PAGE_SIZE page_size page-size pageSize PageSize\n\
+            user_id USER_ID user_id user-id userId UserId"
+        );
+    }
 
-        let actual =
-            Filter::new(Some(options)).unwrap().to_naming_cases_from(words);
-        assert_eq!(actual, vec![NamingCase::Pascal("PageSize".to_string())]);
+    #[test]
+    fn mark_unchanged_converted_strings() {
+        let options = Some(to_string_vec(vec!["s", "c"]));
+        let cases = || vec![naming::which_case("pageSize")];
+
+        let convertor = Convertor::new(options.clone(), cases());
+        assert_eq!(
+            convertor.with_unchanged_marked(true).into_lines(),
+            "pageSize page_size pageSize="
+        );
+
+        let convertor = Convertor::new(options, cases());
+        assert_eq!(
+            convertor.with_unchanged_marked(true).into_json(),
+            r#"{"result":[{"origin":"pageSize","snake":"page_size","camel":"pageSize","changed":{"snake":true,"camel":false}}]}"#
+        );
     }
 
     #[test]
-    fn convert_words_to_naming_cases() {
-        let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
-        let words: Vec<String> = vec![
-            "SCREAMING_SNAKE",
-            "snake_case",
-            "kebab-case",
-            "camelCase",
-            "PascalCase",
-            "-invalid_",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect();
+    fn diff_with_target_case() {
+        let cases = || {
+            vec![naming::which_case("pageSize"), naming::which_case("user_id")]
+        };
+
+        let convertor = Convertor::new(None, cases());
+        assert_eq!(
+            convertor.into_diff(NamingCaseKind::Snake),
+            "pageSize -> page_size"
+        );
 
-        let actual =
-            Filter::new(Some(options)).unwrap().to_naming_cases_from(words);
+        let convertor = Convertor::new(None, cases());
+        assert_eq!(
+            convertor.into_diff_json(NamingCaseKind::Snake),
+            r#"{"result":[{"origin":"pageSize","from":"camel","to":"page_size"}]}"#
+        );
+    }
 
-        let expect = vec![
-            NamingCase::ScreamingSnake("SCREAMING_SNAKE".to_string()),
-            NamingCase::Snake("snake_case".to_string()),
-            NamingCase::Kebab("kebab-case".to_string()),
-            NamingCase::Camel("camelCase".to_string()),
-            NamingCase::Pascal("PascalCase".to_string()),
-        ];
+    #[cfg(feature = "sample")]
+    #[test]
+    fn sample_cases() {
+        let words = ["a_b", "c_d", "e_f", "g_h", "i_j", "k_l"];
+        let convertor = || {
+            let cases = words.iter().map(|word| naming::which_case(word));
+            Convertor::new(Some(to_string_vec(vec!["s"])), cases.collect())
+        };
+
+        let sampled = convertor().sample(3, Some(42)).into_lines();
+        assert_eq!(sampled, convertor().sample(3, Some(42)).into_lines());
+        let origins: Vec<&str> = sampled
+            .lines()
+            .map(|line| line.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(origins.len(), 3);
+        // selected cases keep their origin order.
+        let positions: Vec<usize> = origins
+            .iter()
+            .map(|origin| words.iter().position(|word| word == origin).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
 
-        assert_eq!(actual, expect);
+        assert_eq!(
+            convertor().sample(10, None).into_lines(),
+            convertor().into_lines()
+        );
     }
-}
 
-#[cfg(test)]
-mod convertor_tests {
-    use naming_lib as naming;
+    #[test]
+    fn limit_cases() {
+        let cases = || {
+            vec!["pageSize", "userName"]
+                .into_iter()
+                .map(|word| naming::which_case(word))
+                .collect()
+        };
+        let options = Some(to_string_vec(vec!["s"]));
+        let convertor = Convertor::new(options.clone(), cases()).limit(1);
+        assert_eq!(
+            convertor.into_json(),
+            r#"{"result":[{"origin":"pageSize","snake":"page_size"}]}"#
+        );
+        let convertor = Convertor::new(options, cases()).limit(5);
+        assert_eq!(
+            convertor.into_lines(),
+            "pageSize page_size\nuserName user_name"
+        );
+    }
+
+    #[test]
+    fn output_to_words() {
+        let cases = vec!["getUserName", "PAGE_SIZE"]
+            .into_iter()
+            .map(|word| naming::which_case(word))
+            .collect();
+        let actual = Convertor::new(None, cases).into_words();
+        assert_eq!(actual, "get\nuser\nname\n\npage\nsize");
+    }
+
+    #[test]
+    fn output_to_stats() {
+        let cases = vec!["pageSize", "userName", "page_size"]
+            .into_iter()
+            .map(|word| naming::which_case(word))
+            .collect();
+        let input = InputStats { files: 2, bytes: 30 };
+        let actual = Convertor::new(None, cases).into_stats(&input);
+        let expect = r#"{"stats":{"files":2,"bytes":30,"captures":3,"counts":{"camel":2,"snake":1}}}"#;
+        assert_eq!(actual, expect);
+    }
 
-    use crate::to_string_vec;
+    #[test]
+    fn retain_cases_not_already_in_target() {
+        let cases = vec!["pageSize", "page_size", "userName"]
+            .into_iter()
+            .map(|word| naming::which_case(word))
+            .collect();
+        let actual = Convertor::new(Some(to_string_vec(vec!["s"])), cases)
+            .retain_not_already(NamingCaseKind::Snake)
+            .into_lines();
+        assert_eq!(actual, "pageSize page_size\nuserName user_name");
+    }
 
-    use super::Convertor;
+    #[test]
+    fn empty_cases_output_empty_results() {
+        let convertor = || Convertor::new(None, Vec::new());
+        assert_eq!(convertor().into_lines(), "");
+        assert_eq!(convertor().into_json(), r#"{"result":[]}"#);
+        assert_eq!(convertor().into_regex(), "");
+        assert_eq!(convertor().into_counts_json(), r#"{"counts":{}}"#);
+    }
 
     #[test]
     fn output_to_lines() {
@@ -382,6 +2366,128 @@ PascalCase PASCAL_CASE pascal_case pascal-case pascalCase PascalCase";
         assert_eq!(actual.as_str(), expect);
     }
 
+    #[test]
+    fn output_to_colored_lines() {
+        let options = to_string_vec(vec!["s", "k"]);
+        let cases = vec![naming::which_case("camelCase")];
+
+        let actual = Convertor::new(Some(options), cases).into_colored_lines();
+        assert_eq!(
+            actual,
+            "\x1b[34mcamelCase\x1b[0m \x1b[32mcamel_case\x1b[0m \x1b[33mcamel-case\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn output_custom_format_from_registry() {
+        let registry = ConversionRegistry::default()
+            .with_format("path", |case, _| {
+                case.to_snake().map(|s| s.replace('_', "/"))
+            });
+        let options = to_string_vec(vec!["s", "path"]);
+        let cases = vec![naming::which_case("pageSize")];
+
+        let actual = Convertor::new(Some(options), cases)
+            .with_registry(registry)
+            .into_json();
+        assert_eq!(
+            actual,
+            r#"{"result":[{"origin":"pageSize","snake":"page_size","path":"page/size"}]}"#
+        );
+    }
+
+    #[test]
+    fn csv_and_table_without_header() {
+        let options = || Some(to_string_vec(vec!["s", "k"]));
+        let cases = || vec![naming::which_case("pageSize")];
+
+        let csv = Convertor::new(options(), cases()).with_header(false);
+        assert_eq!(csv.into_csv(','), "pageSize,page_size,page-size");
+        let table = Convertor::new(options(), cases()).with_header(false);
+        assert_eq!(table.into_table(), "pageSize  page_size  page-size");
+        let empty = Convertor::new(options(), Vec::new()).with_header(false);
+        assert_eq!(empty.into_table(), "");
+    }
+
+    #[test]
+    fn convert_pre_split_words() {
+        let filter = Filter::new(Some(to_string_vec(vec!["c"]))).unwrap();
+        let words = to_string_vec(vec!["pageSize", "user_id", "-invalid_"]);
+        let options = Some(to_string_vec(vec!["s"]));
+        assert_eq!(
+            Convertor::from_words(options, words, &filter).into_lines(),
+            "pageSize page_size"
+        );
+    }
+
+    #[test]
+    fn convert_qualified_names_by_segments() {
+        let filter = Filter::new(None).unwrap();
+        let words = to_string_vec(vec!["myObj.getName", "ns::HttpClient"]);
+        let options = Some(to_string_vec(vec!["S", "k", "p"]));
+        assert_eq!(
+            Convertor::from_words(options, words, &filter)
+                .with_affixes(Some("$"), None)
+                .into_lines(),
+            "myObj.getName $MY_OBJ.GET_NAME $my-obj.get-name $MyObj.GetName\n\
+            ns::HttpClient $NS::HTTP_CLIENT $ns::http-client $Ns::HttpClient"
+        );
+    }
+
+    #[test]
+    fn customize_json_keys() {
+        let options = || Some(to_string_vec(vec!["S", "F"]));
+        let cases = || vec![naming::which_case("pageSize")];
+
+        let convertor = Convertor::new(options(), cases())
+            .with_json_keys("camel".parse().unwrap());
+        assert_eq!(
+            convertor.with_unchanged_marked(true).into_json(),
+            r#"{"result":[{"origin":"pageSize","screamingSnake":"PAGE_SIZE","upperFlat":"PAGESIZE","changed":{"screamingSnake":true,"upperFlat":true}}]}"#
+        );
+        let convertor =
+            Convertor::new(options(), cases()).with_json_keys(JsonKeys::Short);
+        assert_eq!(
+            convertor.into_json(),
+            r#"{"result":[{"origin":"pageSize","S":"PAGE_SIZE","F":"PAGESIZE"}]}"#
+        );
+        assert!("kebab".parse::<JsonKeys>().is_err());
+    }
+
+    #[test]
+    fn output_custom_format_from_spec() {
+        let options =
+            to_string_vec(vec!["custom:-:upper", "custom::title:lower"]);
+        let cases = vec![naming::which_case("page_size")];
+
+        let actual = Convertor::new(Some(options), cases).validated().unwrap();
+        assert_eq!(
+            actual.into_json(),
+            r#"{"result":[{"origin":"page_size","custom:-:upper":"PAGE-SIZE","custom::title:lower":"pageSize"}]}"#
+        );
+
+        let options = to_string_vec(vec!["custom:-:camel"]);
+        let err = Convertor::new(Some(options), Vec::new()).validated();
+        assert!(err
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("naming: output `custom:-:camel`: should be custom:"));
+    }
+
+    #[test]
+    fn output_with_affixes() {
+        let options = to_string_vec(vec!["s"]);
+        let cases = vec![naming::which_case("userName")];
+
+        let convertor = Convertor::new(Some(options), cases)
+            .with_affixes(Some("get_"), Some("_t"));
+        assert_eq!(
+            convertor.into_json(),
+            r#"{"result":[{"origin":"userName","snake":"get_user_name_t"}]}"#
+        );
+    }
+
     #[test]
     fn output_bases_on_options_order() {
         let options = to_string_vec(vec!["p", "c", "s", "k", "S"]);
@@ -413,6 +2519,322 @@ PascalCase PASCAL_CASE pascal_case pascal-case pascalCase PascalCase";
         assert_eq!(actual.as_str(), expect);
     }
 
+    #[test]
+    fn convert_into_map() {
+        let options = to_string_vec(vec!["S", "custom:+:lower", "c"]);
+        let cases =
+            vec![naming::which_case("pageSize"), naming::which_case("user_id")];
+
+        let actual = Convertor::new(Some(options), cases)
+            .with_affixes(Some("_"), None)
+            .into_map();
+        let converted = |screaming: &str, camel: &str| {
+            vec![
+                (NamingCaseKind::ScreamingSnake, screaming.to_string()),
+                (NamingCaseKind::Camel, camel.to_string()),
+            ]
+            .into_iter()
+            .collect()
+        };
+        assert_eq!(
+            actual,
+            vec![
+                ("pageSize".to_string(), converted("_PAGE_SIZE", "_pageSize")),
+                ("user_id".to_string(), converted("_USER_ID", "_userId")),
+            ]
+        );
+    }
+
+    #[test]
+    fn output_to_ndjson() {
+        let options = || Some(to_string_vec(vec!["s", "c"]));
+        let cases = || {
+            vec![
+                naming::which_case("snake_case"),
+                naming::which_case("kebab-case"),
+            ]
+        };
+
+        let actual = Convertor::new(options(), cases()).into_ndjson();
+        assert_eq!(
+            actual,
+            concat!(
+                r#"{"origin":"snake_case","snake":"snake_case","camel":"snakeCase"}"#,
+                "\n",
+                r#"{"origin":"kebab-case","snake":"kebab_case","camel":"kebabCase"}"#
+            )
+        );
+        assert_eq!(Convertor::new(options(), Vec::new()).into_ndjson(), "");
+    }
+
+    #[test]
+    fn escape_special_characters_in_json() {
+        let options = to_string_vec(vec!["s"]);
+        let cases = vec![NamingCase::SingleWord("a\"b\\c".to_string())];
+
+        let actual = Convertor::new(Some(options), cases).into_json();
+        assert_eq!(
+            actual,
+            r#"{"result":[{"origin":"a\"b\\c","snake":"a\"b\\c"}]}"#
+        );
+    }
+
+    #[test]
+    fn output_rejected_words_in_json() {
+        let options = to_string_vec(vec!["s"]);
+        let cases = vec![naming::which_case("aA")];
+        let rejected = to_string_vec(vec!["-invalid_"]);
+
+        let convertor = Convertor::new(Some(options), cases);
+        assert_eq!(
+            convertor.with_rejected(rejected).into_json(),
+            r#"{"result":[{"origin":"aA","snake":"a_a"}],"rejected":["-invalid_"]}"#
+        );
+    }
+
+    #[test]
+    fn output_rejected_words_in_pretty_json() {
+        let rejected = to_string_vec(vec!["-a", "-b"]);
+        let actual = Convertor::new(None, vec![])
+            .with_rejected(rejected)
+            .into_pretty_json();
+        assert_eq!(
+            actual,
+            "{\n  \"result\": [],\n  \"rejected\": [\"-a\", \"-b\"]\n}"
+        );
+    }
+
+    #[test]
+    fn output_words_to_json() {
+        let words = to_string_vec(vec!["-a", "b\"c"]);
+        assert_eq!(words_to_json(&words), r#"{"result":["-a","b\"c"]}"#);
+    }
+
+    #[test]
+    fn output_to_yaml() {
+        let options = to_string_vec(vec!["s", "k"]);
+        let words = vec!["snake_case", "camelCase"];
+        let cases =
+            words.into_iter().map(|word| naming::which_case(word)).collect();
+
+        let actual = Convertor::new(Some(options), cases).into_yaml();
+
+        let expect = r#"result:
+  - origin: "snake_case"
+    snake: "snake_case"
+    kebab: "snake-case"
+  - origin: "camelCase"
+    snake: "camel_case"
+    kebab: "camel-case""#;
+
+        assert_eq!(actual.as_str(), expect);
+    }
+
+    #[test]
+    fn output_empty_yaml() {
+        let actual = Convertor::new(None, vec![]).into_yaml();
+        assert_eq!(actual.as_str(), "result: []");
+    }
+
+    #[test]
+    fn output_to_csv() {
+        let options = to_string_vec(vec!["s", "c"]);
+        let words = vec!["snake_case", "camelCase"];
+        let cases =
+            words.into_iter().map(|word| naming::which_case(word)).collect();
+
+        let actual = Convertor::new(Some(options), cases).into_csv(',');
+
+        let expect = "\
+origin,snake,camel
+snake_case,snake_case,snakeCase
+camelCase,camel_case,camelCase";
+        assert_eq!(actual.as_str(), expect);
+    }
+
+    #[test]
+    fn output_to_tsv() {
+        let options = to_string_vec(vec!["k"]);
+        let cases = vec![naming::which_case("camelCase")];
+
+        let actual = Convertor::new(Some(options), cases).into_csv('\t');
+        assert_eq!(actual.as_str(), "origin\tkebab\ncamelCase\tcamel-case");
+    }
+
+    #[test]
+    fn escape_csv_fields() {
+        assert_eq!(Convertor::escape_csv_field("a-b", ','), "a-b");
+        assert_eq!(Convertor::escape_csv_field("a,b", ','), "\"a,b\"");
+        assert_eq!(Convertor::escape_csv_field("a,b", '\t'), "a,b");
+        assert_eq!(Convertor::escape_csv_field("a\"b", ','), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn output_to_pretty_json() {
+        let options = to_string_vec(vec!["s", "c"]);
+        let words = vec!["snake_case", "kebab-case"];
+        let cases =
+            words.into_iter().map(|word| naming::which_case(word)).collect();
+
+        let actual = Convertor::new(Some(options), cases).into_pretty_json();
+
+        let expect = r#"{
+  "result": [
+    {
+      "origin": "snake_case",
+      "snake": "snake_case",
+      "camel": "snakeCase"
+    },
+    {
+      "origin": "kebab-case",
+      "snake": "kebab_case",
+      "camel": "kebabCase"
+    }
+  ]
+}"#;
+        assert_eq!(actual.as_str(), expect);
+    }
+
+    #[test]
+    fn output_empty_pretty_json() {
+        let actual = Convertor::new(None, vec![]).into_pretty_json();
+        assert_eq!(actual.as_str(), "{\n  \"result\": []\n}");
+    }
+
+    #[test]
+    fn dedup_origins_keeps_first_seen_order() {
+        let options = to_string_vec(vec!["s"]);
+        let cases = Filter::new(Some(vec!["h".to_string()]))
+            .unwrap()
+            .to_naming_cases_from(to_string_vec(vec![
+                "intSize", "strName", "strSize",
+            ]));
+
+        let actual =
+            Convertor::new(Some(options), cases).dedup_origins().into_lines();
+        assert_eq!(actual.as_str(), "Size size\nName name");
+    }
+
+    #[test]
+    fn sort_by_origin() {
+        let options = to_string_vec(vec!["s"]);
+        let words = vec!["cC", "a_b", "B_A", "aA"];
+        let cases =
+            words.into_iter().map(|word| naming::which_case(word)).collect();
+
+        let actual = Convertor::new(Some(options), cases)
+            .sort_by(None)
+            .unwrap()
+            .into_lines();
+        assert_eq!(actual.as_str(), "B_A b_a\naA a_a\na_b a_b\ncC c_c");
+    }
+
+    #[test]
+    fn sort_by_format_is_stable() {
+        let options = to_string_vec(vec!["s"]);
+        let words = vec!["cC", "a_b", "A_A", "aA", "aB"];
+        let cases =
+            words.into_iter().map(|word| naming::which_case(word)).collect();
+
+        let actual = Convertor::new(Some(options), cases)
+            .sort_by(Some("s"))
+            .unwrap()
+            .into_lines();
+        assert_eq!(actual.as_str(), "A_A a_a\naA a_a\na_b a_b\naB a_b\ncC c_c");
+    }
+
+    #[test]
+    fn sort_by_custom_or_unknown_format() {
+        let options = to_string_vec(vec!["s"]);
+        let cases = || vec![naming::which_case("bA"), naming::which_case("aB")];
+
+        let actual = Convertor::new(Some(options.clone()), cases())
+            .sort_by(Some("custom:.:lower"))
+            .unwrap()
+            .into_lines();
+        assert_eq!(actual.as_str(), "aB a_b\nbA b_a");
+
+        let convertor = Convertor::new(Some(options), cases());
+        assert!(convertor.sort_by(Some("x")).is_err());
+    }
+
+    #[test]
+    fn output_to_table() {
+        let options = to_string_vec(vec!["s", "k"]);
+        let words = vec!["camelCase", "a"];
+        let cases =
+            words.into_iter().map(|word| naming::which_case(word)).collect();
+
+        let actual = Convertor::new(Some(options), cases).into_table();
+        let expect = "\
+origin     snake       kebab
+camelCase  camel_case  camel-case
+a          a           a";
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn table_measures_display_width() {
+        let rows = to_string_vec(vec!["宽字", "x"]);
+        assert_eq!(Convertor::one_row_to_table(&rows, &[4, 1]), "宽字  x");
+        let rows = to_string_vec(vec!["ab", "x"]);
+        assert_eq!(Convertor::one_row_to_table(&rows, &[4, 1]), "ab    x");
+    }
+
+    #[test]
+    fn output_counts() {
+        let words = vec!["aA", "a_a", "bB", "cC", "b_b", "A_A"];
+        let cases =
+            words.into_iter().map(|word| naming::which_case(word)).collect();
+
+        let convertor = Convertor::new(None, cases);
+        assert_eq!(
+            convertor.into_counts().as_str(),
+            "camel: 3\nsnake: 2\nscreaming_snake: 1"
+        );
+    }
+
+    #[test]
+    fn output_counts_json() {
+        let words = vec!["aA", "a_a", "bB"];
+        let cases =
+            words.into_iter().map(|word| naming::which_case(word)).collect();
+
+        let actual = Convertor::new(None, cases).into_counts_json();
+        assert_eq!(actual.as_str(), r#"{"counts":{"camel":2,"snake":1}}"#);
+    }
+
+    #[test]
+    fn retain_ambiguous_cases() {
+        let words = vec!["abc", "snake_case", "camelCase", "ABC"];
+        let cases =
+            words.into_iter().map(|word| naming::which_case(word)).collect();
+
+        let actual = Convertor::new(None, cases).retain_ambiguous();
+        let origins: Vec<String> =
+            actual.cases.iter().map(|case| case.to_string()).collect();
+        assert_eq!(origins, to_string_vec(vec!["abc", "ABC"]));
+    }
+
+    #[test]
+    fn output_candidates() {
+        let cases = vec![naming::which_case("abc")];
+        let actual = Convertor::new(None, cases).into_candidates();
+        assert!(actual.starts_with("abc single_word "));
+        assert!(actual.contains(" snake"));
+        assert!(actual.contains(" kebab"));
+    }
+
+    #[test]
+    fn output_candidates_json() {
+        let cases = vec![naming::which_case("ab-c")];
+        let actual = Convertor::new(None, cases).into_candidates_json();
+        assert_eq!(
+            actual,
+            r#"{"result":[{"origin":"ab-c","candidates":["kebab"]}]}"#
+        );
+    }
+
     #[test]
     fn output_to_regex() {
         let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
@@ -430,6 +2852,94 @@ snake_case SNAKE_CASE|snake_case|snake-case|snakeCase|SnakeCase";
         assert_eq!(actual.as_str(), expect);
     }
 
+    #[test]
+    fn output_to_regex_with_bounds() {
+        let options = to_string_vec(vec!["s", "d"]);
+        let cases = vec![naming::which_case("aA")];
+
+        let convertor = Convertor::new(Some(options.clone()), cases);
+        assert_eq!(
+            convertor.with_regex_bounds(true, false).into_regex().as_str(),
+            r"aA ^(a_a|a\.a)$"
+        );
+
+        let cases = vec![naming::which_case("aA")];
+        let convertor = Convertor::new(Some(options.clone()), cases);
+        assert_eq!(
+            convertor.with_regex_bounds(false, true).into_regex().as_str(),
+            r"aA \b(a_a|a\.a)\b"
+        );
+
+        let cases = vec![naming::which_case("aA")];
+        let convertor = Convertor::new(Some(options), cases);
+        assert_eq!(
+            convertor.with_regex_bounds(true, true).into_regex_json().as_str(),
+            r#"{"result":[{"origin":"aA","regex":"^\\b(a_a|a\\.a)\\b$"}]}"#
+        );
+    }
+
+    #[test]
+    fn output_to_case_insensitive_regex() {
+        let options = to_string_vec(vec!["S", "s", "c", "p"]);
+        let cases = || vec![naming::which_case("aB")];
+
+        let convertor = Convertor::new(Some(options.clone()), cases());
+        assert_eq!(
+            convertor.with_regex_icase(true).into_regex().as_str(),
+            r"aB (?i)A_B|aB"
+        );
+
+        let convertor = Convertor::new(Some(options), cases());
+        assert_eq!(
+            convertor
+                .with_regex_icase(true)
+                .with_regex_bounds(true, false)
+                .into_regex()
+                .as_str(),
+            r"aB (?i)^(A_B|aB)$"
+        );
+    }
+
+    #[test]
+    fn output_to_wrapped_regex() {
+        let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
+        let cases = || vec![naming::which_case("pageSize")];
+
+        let convertor = Convertor::new(Some(options.clone()), cases());
+        let expect = "pageSize (?x)\n  PAGE_SIZE|page_size|\n  page-size|pageSize|\n  PageSize";
+        assert_eq!(convertor.with_regex_wrap(Some(22)).into_regex(), expect);
+
+        let convertor = Convertor::new(Some(options), cases());
+        let expect = "pageSize (?x)\\b(\n  PAGE_SIZE|\n  page_size|\n  page-size|\n  pageSize|\n  PageSize\n)\\b";
+        assert_eq!(
+            convertor
+                .with_regex_wrap(Some(4))
+                .with_regex_bounds(false, true)
+                .into_regex(),
+            expect
+        );
+    }
+
+    #[test]
+    fn escape_whitespace_in_wrapped_regex() {
+        let options = to_string_vec(vec!["s", "c"]);
+        let cases = vec![naming::which_case("aB")];
+
+        let actual = Convertor::new(Some(options), cases)
+            .with_affixes(Some("# "), None)
+            .with_regex_wrap(Some(80))
+            .into_regex();
+
+        assert_eq!(actual, concat!("aB (?x)\n", r"  \#\ a_b|\#\ aB"));
+    }
+
+    #[test]
+    fn escape_regex_meta_characters() {
+        assert_eq!(escape_regex("a.b"), r"a\.b");
+        assert_eq!(escape_regex("a-b_c"), "a-b_c");
+        assert_eq!(escape_regex("(a|b)"), r"\(a\|b\)");
+    }
+
     #[test]
     fn output_to_regex_json() {
         let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
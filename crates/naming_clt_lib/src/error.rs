@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Errors returned by this crate. The `Display` output is ready to be
+/// printed to users as is, e.g. "naming: stdin: ...".
+#[derive(Debug)]
+pub enum NamingError {
+    /// Both hungarian notation (h) and camel case (c) are in `--filter`.
+    FilterConflict,
+    /// Unknown format letter in `--filter`, with all valid letters.
+    InvalidFilterLetter { letter: String, valid: Vec<String> },
+    /// Unknown format in `--output`, with all valid formats.
+    InvalidOutputFormat { format: String, valid: Vec<String> },
+    /// Neither a letter nor a name of `NamingCaseKind`.
+    UnknownCase(String),
+    /// A `--locator` value that can't be used, and why.
+    InvalidLocator { locator: String, reason: String },
+    /// An option's value that is out of its range,
+    /// e.g. option "jobs", value "0", expected "a positive integer".
+    InvalidOptionValue { option: String, value: String, expected: String },
+    /// A line in the `--abbreviations` file that isn't `word=abbreviation`.
+    InvalidAbbreviation { path: String, line: usize },
+    /// Input given by `--input-format json` isn't this tool's json output.
+    InvalidJsonInput(String),
+    /// No file is given and stdin is a terminal.
+    NoInput,
+    /// Failed to read or write the path ("stdin" for the standard input).
+    IoError { path: String, source: io::Error },
+    /// The option needs a cargo feature that this build doesn't enable.
+    FeatureDisabled { option: String, feature: String },
+    /// Failed to build the thread pool for `--jobs`.
+    ThreadPool(String),
+}
+
+impl fmt::Display for NamingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamingError::FilterConflict => write!(
+                f,
+                "naming: In option --filter, at most one of the two, \
+                hungarian notation (h) and camel case (c) can appear."
+            ),
+            NamingError::InvalidFilterLetter { letter, valid } => write!(
+                f,
+                "naming: In option --filter, unknown format `{}`, \
+                valid formats: {}.",
+                letter,
+                valid.join(",")
+            ),
+            NamingError::InvalidOutputFormat { format, valid } => write!(
+                f,
+                "naming: unknown output format `{}`, valid: {}.",
+                format,
+                valid.join(",")
+            ),
+            NamingError::UnknownCase(case) => {
+                write!(f, "naming: `{}`: unknown naming case.", case)
+            }
+            NamingError::InvalidLocator { locator, reason } => {
+                write!(f, "naming: locator `{}`: {}", locator, reason)
+            }
+            NamingError::InvalidOptionValue { option, value, expected } => {
+                write!(
+                    f,
+                    "naming: {} `{}`: should be {}.",
+                    option, value, expected
+                )
+            }
+            NamingError::InvalidAbbreviation { path, line } => write!(
+                f,
+                "naming: {}: line {}: expect `word=abbreviation`, \
+                both are letters or digits.",
+                path, line
+            ),
+            NamingError::InvalidJsonInput(reason) => write!(
+                f,
+                "naming: input is not a json output of this tool: {}.",
+                reason
+            ),
+            NamingError::NoInput => write!(
+                f,
+                "naming: no input was found. \
+                Enter -h or --help for help information."
+            ),
+            NamingError::IoError { path, source } => {
+                write!(f, "naming: {}: {}", path, source)
+            }
+            NamingError::FeatureDisabled { option, feature } => write!(
+                f,
+                "naming: option --{} needs the `{}` feature, reinstall with \
+                `cargo install naming_clt --features {}`.",
+                option, feature, feature
+            ),
+            NamingError::ThreadPool(msg) => write!(f, "naming: jobs: {}", msg),
+        }
+    }
+}
+
+impl Error for NamingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NamingError::IoError { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl NamingError {
+    pub(crate) fn io(path: &str, source: io::Error) -> NamingError {
+        NamingError::IoError { path: path.to_string(), source }
+    }
+}
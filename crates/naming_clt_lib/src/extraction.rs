@@ -1,15 +1,23 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::io::{self, BufRead};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 use fancy_regex::Regex;
+#[cfg(feature = "normalize")]
+use unicode_normalization::UnicodeNormalization;
+
+use super::json::{self, JsonValue};
+use super::NamingError;
 
 /// Convert Vec<String> into a slice of &str in Rust:
 /// https://stackoverflow.com/a/41180422/11397457
 pub fn read_from_files<T: AsRef<str>>(
     files: &[T],
     logical_eof: Option<&str>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, NamingError> {
     let mut text = Vec::new();
     for file in files {
         text.push(read_file(file.as_ref(), logical_eof)?);
@@ -17,10 +25,337 @@ pub fn read_from_files<T: AsRef<str>>(
     Ok(text)
 }
 
-fn read_file(file: &str, eof: Option<&str>) -> Result<String, String> {
+/// Answer user's `--include`, `--exclude` and `--max-depth` options,
+/// expand directories in given paths into files inside them.
+pub struct DirWalker {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    max_depth: Option<usize>,
+}
+
+impl DirWalker {
+    /// Patterns are globs where `*` matches any characters except "/",
+    /// `**` matches any characters and `?` matches one character.
+    /// A pattern without "/" is matched against the file name,
+    /// otherwise against the path relative to the walked directory.
+    ///
+    /// Files must match any of `include` patterns (if given)
+    /// and none of `exclude` patterns, directories matching
+    /// `exclude` patterns aren't walked into.
+    pub fn new(
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        max_depth: Option<usize>,
+    ) -> DirWalker {
+        DirWalker {
+            include: include.unwrap_or_default(),
+            exclude: exclude.unwrap_or_default(),
+            max_depth,
+        }
+    }
+
+    /// Replace each directory in given paths with files found by walking
+    /// it recursively (sorted by name), other paths are kept as is.
+    /// `max_depth` 1 means only files directly inside the directory.
+    /// A directory is never walked twice, which guards against symlink loops.
+    pub fn expand<T: AsRef<str>>(
+        &self,
+        paths: &[T],
+    ) -> Result<Vec<String>, NamingError> {
+        let mut files = Vec::new();
+        let mut visited = HashSet::new();
+        for path in paths {
+            let path = path.as_ref();
+            if Path::new(path).is_dir() {
+                self.walk(Path::new(path), "", 1, &mut visited, &mut files)?;
+            } else {
+                files.push(path.to_string());
+            }
+        }
+        Ok(files)
+    }
+
+    fn walk(
+        &self,
+        dir: &Path,
+        relative: &str,
+        depth: usize,
+        visited: &mut HashSet<PathBuf>,
+        files: &mut Vec<String>,
+    ) -> Result<(), NamingError> {
+        let error =
+            |msg: io::Error| NamingError::io(&dir.to_string_lossy(), msg);
+        if matches!(self.max_depth, Some(max) if depth > max)
+            || !visited.insert(fs::canonicalize(dir).map_err(error)?)
+        {
+            return Ok(());
+        }
+
+        let mut entries = fs::read_dir(dir)
+            .map_err(error)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<PathBuf>, io::Error>>()
+            .map_err(error)?;
+        entries.sort();
+
+        for path in entries {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let relative = if relative.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}/{}", relative, name)
+            };
+            let matches_any = |patterns: &[String]| {
+                patterns
+                    .iter()
+                    .any(|pattern| glob_matches(pattern, &name, &relative))
+            };
+            if matches_any(&self.exclude) {
+                continue;
+            }
+            if path.is_dir() {
+                self.walk(&path, &relative, depth + 1, visited, files)?;
+            } else if self.include.is_empty() || matches_any(&self.include) {
+                files.push(path.to_string_lossy().to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn glob_matches(pattern: &str, name: &str, relative: &str) -> bool {
+    let text = if pattern.contains('/') { relative } else { name };
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_matches_chars(&pattern, &text)
+}
+
+fn glob_matches_chars(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => match rest.split_first() {
+            Some(('*', rest)) => {
+                (0..=text.len()).any(|i| glob_matches_chars(rest, &text[i..]))
+            }
+            // a single star doesn't cross directory separators.
+            _ => (0..=text.len())
+                .take_while(|&i| i == 0 || text[i - 1] != '/')
+                .any(|i| glob_matches_chars(rest, &text[i..])),
+        },
+        Some(('?', rest)) => matches!(
+            text.split_first(),
+            Some((c, text)) if *c != '/' && glob_matches_chars(rest, text)
+        ),
+        Some((p, rest)) => matches!(
+            text.split_first(),
+            Some((c, text)) if c == p && glob_matches_chars(rest, text)
+        ),
+    }
+}
+
+/// Answer user's `--input-format json` option, read back origin strings
+/// from this tool's json output, i.e. a "result" array whose elements
+/// are either strings or objects with an "origin" string field.
+/// Origin strings are returned in order, one per line.
+pub fn origins_from_json(text: &str) -> Result<String, NamingError> {
+    let error =
+        |reason: &str| NamingError::InvalidJsonInput(reason.to_string());
+    let value = json::parse(text).map_err(|err| error(&err))?;
+    let result = match value.get("result") {
+        Some(JsonValue::Array(result)) => result,
+        _ => return Err(error("expect a \"result\" array")),
+    };
+
+    let mut origins = Vec::with_capacity(result.len());
+    for element in result {
+        match element.get("origin").unwrap_or(element) {
+            JsonValue::String(origin) => origins.push(origin.as_str()),
+            _ => {
+                return Err(error(
+                    "expect strings or objects with an \"origin\" string",
+                ))
+            }
+        }
+    }
+    Ok(origins.join("\n"))
+}
+
+/// Answer user's `--abbreviations` option, read the word -> abbreviation
+/// map from given file, one `word=abbreviation` per line (case insensitive,
+/// both are letters or digits), blank lines and lines starting with "#"
+/// are ignored.
+pub fn read_abbreviations(
+    path: &str,
+) -> Result<HashMap<String, String>, NamingError> {
+    let text =
+        fs::read_to_string(path).map_err(|msg| NamingError::io(path, msg))?;
+    parse_abbreviations(&text, path)
+}
+
+fn parse_abbreviations(
+    text: &str,
+    path: &str,
+) -> Result<HashMap<String, String>, NamingError> {
+    let is_word = |word: &str| {
+        !word.is_empty() && word.chars().all(|c| c.is_ascii_alphanumeric())
+    };
+    let mut abbreviations = HashMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((word, abbr))
+                if is_word(word.trim()) && is_word(abbr.trim()) =>
+            {
+                abbreviations.insert(
+                    word.trim().to_lowercase(),
+                    abbr.trim().to_lowercase(),
+                );
+            }
+            _ => {
+                return Err(NamingError::InvalidAbbreviation {
+                    path: path.to_string(),
+                    line: index + 1,
+                })
+            }
+        }
+    }
+    Ok(abbreviations)
+}
+
+/// Size of the input, reported by `--stats` option.
+#[derive(Debug, Default, PartialEq)]
+pub struct InputStats {
+    /// Number of files read, 0 when reading from stdin.
+    pub files: usize,
+    /// Bytes of text processed, after applying the logical EOF.
+    pub bytes: usize,
+}
+
+impl InputStats {
+    pub fn of(text: &[String], files: usize) -> InputStats {
+        InputStats { files, bytes: text.iter().map(|t| t.len()).sum() }
+    }
+}
+
+/// Same as `read_from_files`, but files are read in parallel with `jobs`
+/// threads when the `parallel` feature is enabled and `jobs` > 1,
+/// the result still keeps the order of given files.
+pub fn read_from_files_in_parallel<T: AsRef<str> + Sync>(
+    files: &[T],
+    logical_eof: Option<&str>,
+    jobs: usize,
+) -> Result<Vec<String>, NamingError> {
+    #[cfg(feature = "parallel")]
+    {
+        if jobs > 1 {
+            use rayon::prelude::*;
+            return run_in_thread_pool(jobs, || {
+                files
+                    .par_iter()
+                    .map(|file| read_file(file.as_ref(), logical_eof))
+                    .collect()
+            })?;
+        }
+    }
+    let _ = jobs;
+    read_from_files(files, logical_eof)
+}
+
+/// Same as `read_from_files_in_parallel`, but files that aren't valid
+/// UTF-8 (e.g. binary files) are skipped instead of failing the read.
+/// Returns text of read files and names of skipped files, both in order.
+pub fn read_text_files_in_parallel<T: AsRef<str> + Sync>(
+    files: &[T],
+    logical_eof: Option<&str>,
+    jobs: usize,
+) -> Result<(Vec<String>, Vec<String>), NamingError> {
+    let contents: Vec<Option<String>> = {
+        #[cfg(feature = "parallel")]
+        {
+            if jobs > 1 {
+                use rayon::prelude::*;
+                run_in_thread_pool(jobs, || {
+                    files
+                        .par_iter()
+                        .map(|file| read_text_file(file.as_ref(), logical_eof))
+                        .collect::<Result<_, NamingError>>()
+                })??
+            } else {
+                read_text_files(files, logical_eof)?
+            }
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            let _ = jobs;
+            read_text_files(files, logical_eof)?
+        }
+    };
+
+    let mut text = Vec::new();
+    let mut skipped = Vec::new();
+    for (file, content) in files.iter().zip(contents) {
+        match content {
+            Some(content) => text.push(content),
+            None => skipped.push(file.as_ref().to_string()),
+        }
+    }
+    Ok((text, skipped))
+}
+
+fn read_text_files<T: AsRef<str>>(
+    files: &[T],
+    eof: Option<&str>,
+) -> Result<Vec<Option<String>>, NamingError> {
+    files.iter().map(|file| read_text_file(file.as_ref(), eof)).collect()
+}
+
+// None if the file isn't valid UTF-8.
+fn read_text_file(
+    file: &str,
+    eof: Option<&str>,
+) -> Result<Option<String>, NamingError> {
+    let bytes = fs::read(file).map_err(|msg| NamingError::io(file, msg))?;
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(Some(prepare_text(eof, text))),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn run_in_thread_pool<R, F>(jobs: usize, op: F) -> Result<R, NamingError>
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => Ok(pool.install(op)),
+        Err(msg) => Err(NamingError::ThreadPool(msg.to_string())),
+    }
+}
+
+fn read_file(file: &str, eof: Option<&str>) -> Result<String, NamingError> {
     match fs::read_to_string(file) {
-        Ok(text) => Ok(trim_text_with_logical_eof(eof, text)),
-        Err(msg) => Err(format!("naming: {}: {}", file, msg)),
+        Ok(text) => Ok(prepare_text(eof, text)),
+        Err(msg) => Err(NamingError::io(file, msg)),
+    }
+}
+
+/// Normalize line endings of the read text, then cut it at the logical EOF.
+fn prepare_text(eof: Option<&str>, text: String) -> String {
+    trim_text_with_logical_eof(eof, normalize_line_endings(text))
+}
+
+/// Replace "\r\n" (Windows) and bare "\r" (classic Mac OS) line endings
+/// with "\n", so carriage returns are never captured into words
+/// (e.g. by a custom "regex:" locator).
+fn normalize_line_endings(text: String) -> String {
+    if text.contains('\r') {
+        text.replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        text
     }
 }
 
@@ -34,65 +369,542 @@ fn trim_text_with_logical_eof(eof: Option<&str>, text: String) -> String {
     }
 }
 
-pub fn read_from_std_in(eof: Option<&str>) -> Result<String, String> {
+pub fn read_from_std_in(eof: Option<&str>) -> Result<String, NamingError> {
     read_from_input(io::stdin().lock(), eof)
 }
 
+/// Answer user's `--files-from` option, read paths from the given list
+/// file ("-" for stdin), one per line, or NUL-delimited if `nul` is true
+/// (e.g. output of `find -print0`). Empty entries are ignored,
+/// and it's an error if any listed path doesn't exist.
+pub fn read_file_list(
+    source: &str,
+    nul: bool,
+) -> Result<Vec<String>, NamingError> {
+    let list = if source == "-" {
+        read_from_input(io::stdin().lock(), None)?
+    } else {
+        let text = fs::read_to_string(source)
+            .map_err(|msg| NamingError::io(source, msg))?;
+        normalize_line_endings(text)
+    };
+    let paths = split_file_list(&list, nul);
+    match paths.iter().find(|path| !Path::new(path).exists()) {
+        Some(missing) => Err(NamingError::io(
+            missing,
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "listed in `{}`, but no such file or directory",
+                    source
+                ),
+            ),
+        )),
+        None => Ok(paths),
+    }
+}
+
+fn split_file_list(list: &str, nul: bool) -> Vec<String> {
+    let separator = if nul { '\0' } else { '\n' };
+    list.split(separator)
+        .filter(|path| !path.is_empty())
+        .map(|path| path.to_string())
+        .collect()
+}
+
+/// Same as `read_from_std_in`, but split the input into NUL-delimited
+/// records (e.g. output of `find -print0`), empty records are dropped.
+/// Each record is treated like the content of a separate file.
+pub fn read_nul_delimited_from_std_in(
+    eof: Option<&str>,
+) -> Result<Vec<String>, NamingError> {
+    read_nul_delimited_from_input(io::stdin().lock(), eof)
+}
+
+fn read_nul_delimited_from_input<R>(
+    input: R,
+    eof: Option<&str>,
+) -> Result<Vec<String>, NamingError>
+where
+    R: BufRead,
+{
+    Ok(read_from_input(input, eof)?
+        .split('\0')
+        .filter(|record| !record.is_empty())
+        .map(|record| record.to_string())
+        .collect())
+}
+
 /// How to test stdin https://stackoverflow.com/a/28370712/11397457
-fn read_from_input<R>(mut input: R, eof: Option<&str>) -> Result<String, String>
+fn read_from_input<R>(
+    mut input: R,
+    eof: Option<&str>,
+) -> Result<String, NamingError>
 where
     R: BufRead,
 {
     let mut buffer = String::new();
     match input.read_to_string(&mut buffer) {
-        Ok(_) => Ok(trim_text_with_logical_eof(eof, buffer)),
-        Err(msg) => Err(format!("naming: stdin: {}", msg)),
+        Ok(_) => Ok(prepare_text(eof, buffer)),
+        Err(msg) => Err(NamingError::io("stdin", msg)),
+    }
+}
+
+/// Byte ranges of code in markdown text: contents of fenced code blocks
+/// (fence lines excluded, so are language tags on them)
+/// and of inline code spans outside fenced code blocks.
+fn markdown_code_spans(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    // where the text outside fenced code blocks starts.
+    let mut prose_start = 0;
+    // (fence character, fence length, content start) of the open fence.
+    let mut fence: Option<(char, usize, usize)> = None;
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let line_end = line_start + line.len();
+        let trimmed = line.trim_start_matches(' ');
+        let indented = line.len() - trimmed.len() > 3;
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~');
+        // fence characters are ASCII, so chars are bytes.
+        let run = marker
+            .map(|m| trimmed.chars().take_while(|c| *c == m).count())
+            .unwrap_or(0);
+        match fence {
+            // a backtick fence's info string can't contain backticks.
+            None if !indented
+                && run >= 3
+                && !(marker == Some('`') && trimmed[run..].contains('`')) =>
+            {
+                spans.extend(inline_code_spans(
+                    &text[prose_start..line_start],
+                    prose_start,
+                ));
+                fence = marker.map(|m| (m, run, line_end));
+            }
+            // the closing fence is at least as long as the opening one.
+            Some((m, len, content_start))
+                if !indented
+                    && marker == Some(m)
+                    && run >= len
+                    && trimmed[run..].trim().is_empty() =>
+            {
+                spans.push(content_start..line_start);
+                fence = None;
+                prose_start = line_end;
+            }
+            _ => {}
+        }
+        line_start = line_end;
+    }
+    match fence {
+        // an unclosed fence runs to the end of text.
+        Some((_, _, content_start)) => spans.push(content_start..text.len()),
+        None => {
+            spans.extend(inline_code_spans(&text[prose_start..], prose_start))
+        }
+    }
+    spans
+}
+
+/// Byte ranges (shifted by `offset`) of inline code spans' contents.
+/// A span opened by a run of backticks is closed by the next run of
+/// the same length, so it can contain shorter runs, e.g. "`` a`b ``".
+fn inline_code_spans(text: &str, offset: usize) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let backticks =
+        |from: usize| bytes[from..].iter().take_while(|b| **b == b'`').count();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'`' {
+            i += 1;
+            continue;
+        }
+        let run = backticks(i);
+        let content_start = i + run;
+        let mut j = content_start;
+        let mut closing = None;
+        while j < bytes.len() {
+            if bytes[j] != b'`' {
+                j += 1;
+                continue;
+            }
+            let length = backticks(j);
+            if length == run {
+                closing = Some(j);
+                break;
+            }
+            j += length;
+        }
+        match closing {
+            Some(end) => {
+                spans.push(offset + content_start..offset + end);
+                i = end + run;
+            }
+            // an unmatched run is literal backticks.
+            None => i = content_start,
+        }
+    }
+    spans
+}
+
+// Comment syntax of languages for the "skip-comments:<lang>" locator.
+#[derive(Clone, Copy)]
+enum CommentSyntax {
+    // "//" line comments and "/* */" block comments, e.g. C, Rust, Java.
+    CStyle,
+    // "#" line comments, e.g. shell, Python.
+    Hash,
+}
+
+impl CommentSyntax {
+    const LANGUAGES: [(&'static str, CommentSyntax); 9] = [
+        ("c", CommentSyntax::CStyle),
+        ("cpp", CommentSyntax::CStyle),
+        ("java", CommentSyntax::CStyle),
+        ("js", CommentSyntax::CStyle),
+        ("go", CommentSyntax::CStyle),
+        ("rust", CommentSyntax::CStyle),
+        ("shell", CommentSyntax::Hash),
+        ("python", CommentSyntax::Hash),
+        ("ruby", CommentSyntax::Hash),
+    ];
+
+    fn of(language: &str) -> Option<CommentSyntax> {
+        CommentSyntax::LANGUAGES
+            .iter()
+            .find(|(name, _)| *name == language)
+            .map(|(_, syntax)| *syntax)
     }
 }
 
+/// Byte ranges of text outside comments of given syntax.
+/// String literals are skipped over, so that comment-like sequences
+/// inside them (e.g. "http://", "#fff") don't start a comment.
+fn uncommented_spans(text: &str, syntax: CommentSyntax) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let line_end =
+        |from: usize| text[from..].find('\n').map_or(text.len(), |i| from + i);
+    let mut spans = Vec::new();
+    let mut code_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let comment_end = match syntax {
+            CommentSyntax::CStyle if bytes[i..].starts_with(b"//") => {
+                line_end(i)
+            }
+            // an unclosed block comment runs to the end of text.
+            CommentSyntax::CStyle if bytes[i..].starts_with(b"/*") => text
+                [i + 2..]
+                .find("*/")
+                .map_or(text.len(), |end| i + 2 + end + 2),
+            // not a comment in "$#" or "${#array[@]}" of shell.
+            CommentSyntax::Hash
+                if bytes[i] == b'#'
+                    && !(i > 0 && matches!(bytes[i - 1], b'$' | b'{')) =>
+            {
+                line_end(i)
+            }
+            _ => {
+                i = skip_literal(text, i, syntax);
+                continue;
+            }
+        };
+        spans.push(code_start..i);
+        code_start = comment_end;
+        i = comment_end;
+    }
+    spans.push(code_start..text.len());
+    spans
+}
+
+/// The index right after the string (or char) literal starting at `start`,
+/// or `start + 1` if no literal starts there.
+/// A literal may span lines (e.g. Python's triple-quoted strings, which
+/// are seen as empty strings around a string), an unclosed one runs to
+/// the end of text.
+fn skip_literal(text: &str, start: usize, syntax: CommentSyntax) -> usize {
+    let bytes = text.as_bytes();
+    let quote = bytes[start];
+    let is_literal = match (quote, syntax) {
+        (b'"', _) | (b'\'', CommentSyntax::Hash) => true,
+        // in C-style languages, single quotes enclose one (maybe escaped)
+        // character, otherwise it's e.g. a lifetime of Rust ('a).
+        (b'\'', CommentSyntax::CStyle) => {
+            let mut chars = text[start + 1..].chars();
+            match chars.next() {
+                Some('\\') => true,
+                Some(_) => chars.next() == Some('\''),
+                None => false,
+            }
+        }
+        _ => false,
+    };
+    if !is_literal {
+        return start + 1;
+    }
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Forms of `--locator` option's values that `Captor` supports.
+pub const LOCATOR_MODES: [&str; 10] = [
+    "<prefix>{}<suffix>",
+    "regex:<pattern>",
+    "string-literal",
+    "markdown-code",
+    "split-glued[:<hint>|<hint>...]",
+    "split-digits",
+    "qualified",
+    "path",
+    "skip-comments:<lang>",
+    "mixed-split",
+];
+
 /// Answer user's `--locator` options,
 /// capture words that match the options from given long text.
 pub struct Captor {
-    patterns: Vec<Regex>,
+    locators: Vec<Locator>,
+    // NFKC normalize text before capturing, see `with_normalization`.
+    #[cfg(feature = "normalize")]
+    normalize: bool,
+}
+
+// How a `--locator` value finds words in text.
+enum Locator {
+    // the first group (or the whole match) of the pattern is the word.
+    Pattern(Regex),
+    // words inside single- or double-quoted string literals.
+    StringLiteral,
+    // words inside markdown fenced code blocks and inline code spans.
+    MarkdownCode,
+    // words located same as the default locator, then split where
+    // identifiers seem glued together, see `Captor::split_glued`.
+    SplitGlued(Vec<String>),
+    // words located same as the default locator, then split on digits,
+    // which are dropped, see `Captor::split_digits`.
+    SplitDigits,
+    // words located same as the default locator, along with the
+    // qualified names they form, e.g. "obj.method", "ns::Type".
+    Qualified,
+    // words located same as the default locator, along with the
+    // path/case words they form with "/", e.g. "user/profile".
+    Path,
+    // words located same as the default locator, outside comments.
+    SkipComments(CommentSyntax),
+    // words located same as the default locator, then split on
+    // separators and case boundaries at once, see `Captor::split_mixed`.
+    SplitMixed,
+}
+
+lazy_static! {
+    // a quoted span, where a backslash escapes the next character (e.g. "a\"b").
+    static ref STRING_LITERAL: Regex =
+        Regex::new(r#"(?s)"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'"#).unwrap();
+
+    // words inside string literals (or glued words)
+    // are located same as the default locator.
+    static ref LITERAL_WORD: Regex =
+        Regex::new(r"(?<=\b)([\p{L}\p{N}_-]+)(?=\b)").unwrap();
+
+    // segments after a qualifier start with a letter or "_",
+    // so that numbers like "3.14" aren't taken as qualified names.
+    static ref QUALIFIED_WORD: Regex = Regex::new(
+        r"(?<=\b)([\p{L}\p{N}_-]+(?:(?:\.|::)[\p{L}_][\p{L}\p{N}_-]*)*)(?=\b)"
+    )
+    .unwrap();
+
+    // words joined by "/" are captured as one path/case word.
+    static ref PATH_WORD: Regex =
+        Regex::new(r"(?<=\b)([\p{L}\p{N}_-]+(?:/[\p{L}\p{N}_-]+)*)(?=\b)")
+            .unwrap();
 }
 
 impl Captor {
     /// Options should be manually escaped by user.
+    /// An option starting with "regex:" is used as a whole pattern,
+    /// its first capture group (or the whole match) is taken as the word.
+    /// The "string-literal" option captures words inside quoted strings.
+    /// The "markdown-code" option captures words inside markdown code.
+    /// The "split-glued" option (optionally with hint words, e.g.
+    /// "split-glued:get|set") splits identifiers glued together.
+    /// The "split-digits" option splits identifiers on digits.
+    /// The "qualified" option captures qualified names (e.g. "obj.method")
+    /// as single words.
+    /// The "path" option captures path/case words (e.g. "user/profile")
+    /// as single words.
+    /// The "skip-comments:<lang>" option (e.g. "skip-comments:rust")
+    /// ignores words inside comments of the language.
+    /// The "mixed-split" option splits identifiers on every boundary.
     /// If there is a locator pair which couldn't be converted to regex, return an Err.
-    pub fn new(locators: Option<Vec<String>>) -> Result<Captor, String> {
+    pub fn new(locators: Option<Vec<String>>) -> Result<Captor, NamingError> {
         // TODO 手册里写双括号来代表单词
         // Set default locator as word edge '\b'.
         let locators =
             locators.unwrap_or_else(|| vec![r"(?<=\b){}(?=\b)".to_string()]);
-        let mut patterns = Vec::new();
+        let mut built = Vec::new();
         for locator in locators {
-            patterns.push(Captor::build_pattern_from(locator)?);
+            built.push(Captor::build_locator_from(locator)?);
         }
-        Ok(Captor { patterns })
+        Ok(Captor {
+            locators: built,
+            #[cfg(feature = "normalize")]
+            normalize: false,
+        })
     }
 
-    fn build_pattern_from(locator: String) -> Result<Regex, String> {
+    fn build_locator_from(locator: String) -> Result<Locator, NamingError> {
+        if locator == "string-literal" {
+            return Ok(Locator::StringLiteral);
+        }
+        if locator == "markdown-code" {
+            return Ok(Locator::MarkdownCode);
+        }
+        if locator == "split-digits" {
+            return Ok(Locator::SplitDigits);
+        }
+        if locator == "qualified" {
+            return Ok(Locator::Qualified);
+        }
+        if locator == "path" {
+            return Ok(Locator::Path);
+        }
+        if locator == "mixed-split" {
+            return Ok(Locator::SplitMixed);
+        }
+        if let Some(language) = locator.strip_prefix("skip-comments:") {
+            return match CommentSyntax::of(language) {
+                Some(syntax) => Ok(Locator::SkipComments(syntax)),
+                None => Err(NamingError::InvalidLocator {
+                    reason: format!(
+                        "unknown language `{}`, valid languages: {}.",
+                        language,
+                        CommentSyntax::LANGUAGES
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .collect::<Vec<&str>>()
+                            .join(",")
+                    ),
+                    locator,
+                }),
+            };
+        }
+        if locator == "split-glued" {
+            return Ok(Locator::SplitGlued(Vec::new()));
+        }
+        if let Some(hints) = locator.strip_prefix("split-glued:") {
+            let hints = hints
+                .split('|')
+                .filter(|hint| !hint.is_empty())
+                .map(String::from)
+                .collect();
+            return Ok(Locator::SplitGlued(hints));
+        }
+        Ok(Locator::Pattern(Captor::build_pattern_from(locator)?))
+    }
+
+    fn build_pattern_from(locator: String) -> Result<Regex, NamingError> {
+        // a whole custom pattern, e.g. "regex:\$[a-zA-Z_]\w*".
+        if let Some(pattern) = locator.strip_prefix("regex:") {
+            return Captor::compile(pattern, &locator);
+        }
+
         let pair = locator.split_once("{}");
         if pair.is_none() {
-            return Err(format!(
-                "naming: locator `{}`: can't split locator pair from this.",
-                locator
-            ));
+            return Err(NamingError::InvalidLocator {
+                locator,
+                reason: "can't split locator pair from this.".to_string(),
+            });
         }
         let pair = pair.unwrap();
 
-        // Currently, we use `([a-zA-Z0-9_-]+)` to match words, it's ok for now,
-        // because it could match words with any naming format.
-        Ok(Regex::new(&format!(r"{}([a-zA-Z0-9_-]+){}", pair.0, pair.1))
-            .unwrap())
+        // Currently, we use `([\p{L}\p{N}_-]+)` to match words, it's ok for now,
+        // because it could match words with any naming format,
+        // in any language (e.g. "straße_name").
+        Captor::compile(
+            &format!(r"{}([\p{{L}}\p{{N}}_-]+){}", pair.0, pair.1),
+            &locator,
+        )
+    }
+
+    fn compile(pattern: &str, locator: &str) -> Result<Regex, NamingError> {
+        Regex::new(pattern).map_err(|err| NamingError::InvalidLocator {
+            locator: locator.to_string(),
+            reason: format!("invalid regex: {}", err),
+        })
+    }
+
+    /// Answer user's `--normalize` option, apply Unicode NFKC normalization
+    /// to text before capturing words, so that compatibility forms
+    /// (e.g. full-width "ｆｏｏBar") are captured and classified as their
+    /// plain counterparts ("fooBar"), and combining marks are composed.
+    ///
+    /// Only words are affected, `replace_words` works on the text as is.
+    #[cfg(feature = "normalize")]
+    pub fn with_normalization(mut self, normalize: bool) -> Captor {
+        self.normalize = normalize;
+        self
+    }
+
+    fn normalized<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        #[cfg(feature = "normalize")]
+        {
+            if self.normalize {
+                return Cow::Owned(text.nfkc().collect());
+            }
+        }
+        Cow::Borrowed(text)
     }
 
     /// Extract words from given long text string,
     /// with regular expression and given locating prefix & suffix.
+    ///
+    /// Results of all locators are merged in locator order. When spans
+    /// captured by different locators overlap, the one from the former
+    /// locator wins and the latter is dropped.
     pub fn capture_words(&self, text: Vec<String>) -> Vec<String> {
         // apply matching on each file's content
-        let mut matches = self.get_matches_from(text);
+        let matches: Vec<String> = text
+            .iter()
+            .flat_map(|t| self.capture_words_without_overlaps(t))
+            .collect();
+        Captor::dedup(matches)
+    }
 
+    /// Same as `capture_words`, but each file's content is matched
+    /// in parallel with `jobs` threads when the `parallel` feature is
+    /// enabled and `jobs` > 1, the result is same as `capture_words`.
+    pub fn capture_words_in_parallel(
+        &self,
+        text: Vec<String>,
+        jobs: usize,
+    ) -> Result<Vec<String>, NamingError> {
+        #[cfg(feature = "parallel")]
+        {
+            if jobs > 1 {
+                use rayon::prelude::*;
+                let matches = run_in_thread_pool(jobs, || {
+                    text.par_iter()
+                        .flat_map_iter(|t| {
+                            self.capture_words_without_overlaps(t)
+                        })
+                        .collect()
+                })?;
+                return Ok(Captor::dedup(matches));
+            }
+        }
+        let _ = jobs;
+        Ok(self.capture_words(text))
+    }
+
+    fn dedup(mut matches: Vec<String>) -> Vec<String> {
         // dedup while keep the order, what an elegant solution:
         // https://users.rust-lang.org/t/deduplicate-vector-in-place-while-preserving-order/56568/6
         let mut set = HashSet::new();
@@ -100,28 +912,354 @@ impl Captor {
         matches
     }
 
-    fn get_matches_from(&self, text: Vec<String>) -> Vec<String> {
-        text.iter()
-            .map(|t| {
-                // for each file's content, apply all patterns on it.
-                self.patterns
-                    .iter()
-                    .map(move |pattern| {
-                        pattern.captures_iter(t).into_iter().map(|cap| {
-                            cap.unwrap().get(1).unwrap().as_str().to_string()
-                        })
+    /// Words of one file's content, in locator order, dropping spans
+    /// that overlap a span kept from a former locator.
+    fn capture_words_without_overlaps(&self, text: &str) -> Vec<String> {
+        let text = self.normalized(text);
+        // kept spans, start -> end, they never overlap each other.
+        let mut kept: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut words = Vec::new();
+        for (span, word) in self.capture_words_with_spans(&text) {
+            // as kept spans are disjoint, only the last one starting
+            // before this span's end could overlap it.
+            let overlapping = matches!(
+                kept.range(..span.end).next_back(),
+                Some((_, &end)) if end > span.start
+            );
+            if !overlapping {
+                kept.insert(span.start, span.end);
+                words.push(word);
+            }
+        }
+        words
+    }
+
+    /// Same as `capture_words`, but for one file's content, and keep
+    /// each word along with its byte range in the given text.
+    /// Words are neither deduplicated nor sorted by position,
+    /// the order is based on locator order, same as `capture_words`.
+    pub fn capture_words_with_spans(
+        &self,
+        text: &str,
+    ) -> Vec<(Range<usize>, String)> {
+        self.locators
+            .iter()
+            .flat_map(|locator| match locator {
+                Locator::Pattern(pattern) => {
+                    Captor::captures_of(pattern, text, 0)
+                }
+                Locator::StringLiteral => STRING_LITERAL
+                    .find_iter(text)
+                    .flat_map(|literal| {
+                        let literal = literal.unwrap();
+                        // exclude the quotes on both sides.
+                        let start = literal.start() + 1;
+                        let content = &text[start..literal.end() - 1];
+                        Captor::captures_of(&LITERAL_WORD, content, start)
+                    })
+                    .collect(),
+                Locator::MarkdownCode => markdown_code_spans(text)
+                    .into_iter()
+                    .flat_map(|span| {
+                        let content = &text[span.clone()];
+                        Captor::captures_of(&LITERAL_WORD, content, span.start)
                     })
-                    .flatten()
-                // now get one file's matches
+                    .collect(),
+                Locator::SplitGlued(hints) => {
+                    Captor::captures_of(&LITERAL_WORD, text, 0)
+                        .into_iter()
+                        .flat_map(|(span, word)| {
+                            Captor::split_glued(&word, hints).into_iter().map(
+                                move |part| {
+                                    let start = span.start + part.start;
+                                    let end = span.start + part.end;
+                                    (start..end, word[part].to_string())
+                                },
+                            )
+                        })
+                        .collect()
+                }
+                Locator::Qualified => {
+                    Captor::captures_of(&QUALIFIED_WORD, text, 0)
+                }
+                Locator::Path => Captor::captures_of(&PATH_WORD, text, 0),
+                Locator::SkipComments(syntax) => {
+                    uncommented_spans(text, *syntax)
+                        .into_iter()
+                        .flat_map(|span| {
+                            let code = &text[span.clone()];
+                            Captor::captures_of(&LITERAL_WORD, code, span.start)
+                        })
+                        .collect()
+                }
+                Locator::SplitMixed => {
+                    Captor::captures_of(&LITERAL_WORD, text, 0)
+                        .into_iter()
+                        .flat_map(|(span, word)| {
+                            Captor::split_mixed(&word).into_iter().map(
+                                move |part| {
+                                    let start = span.start + part.start;
+                                    let end = span.start + part.end;
+                                    (start..end, word[part].to_string())
+                                },
+                            )
+                        })
+                        .collect()
+                }
+                Locator::SplitDigits => {
+                    Captor::captures_of(&LITERAL_WORD, text, 0)
+                        .into_iter()
+                        .flat_map(|(span, word)| {
+                            Captor::split_digits(&word).into_iter().map(
+                                move |part| {
+                                    let start = span.start + part.start;
+                                    let end = span.start + part.end;
+                                    (start..end, word[part].to_string())
+                                },
+                            )
+                        })
+                        .collect()
+                }
             })
-            .flatten()
             .collect()
     }
+
+    /// Split a word on runs of digits, which are dropped along with
+    /// separators next to them, return the ranges of the remaining parts
+    /// in the word, e.g. "config2json3yaml" -> "config", "json", "yaml",
+    /// "utf8_string" -> "utf", "string".
+    fn split_digits(word: &str) -> Vec<Range<usize>> {
+        let is_separator = |c: char| c == '_' || c == '-';
+        word.split(char::is_numeric)
+            .filter_map(|part| {
+                let trimmed = part.trim_matches(is_separator);
+                if trimmed.is_empty() {
+                    return None;
+                }
+                // parts and trimmed parts are slices of the word.
+                let start = trimmed.as_ptr() as usize - word.as_ptr() as usize;
+                Some(start..start + trimmed.len())
+            })
+            .collect()
+    }
+
+    /// Split a word on every boundary at once, return the ranges of
+    /// the parts in the word. Boundaries are separators ("_" and "-",
+    /// which are dropped), and case boundaries, see `split_acronym_words`.
+    /// Digits are attached to their neighbors,
+    /// e.g. "XMLData_parser-v2" -> "XML", "Data", "parser", "v2".
+    fn split_mixed(word: &str) -> Vec<Range<usize>> {
+        let mut parts = Vec::new();
+        let mut offset = 0;
+        for segment in word.split(['_', '-']) {
+            for part in super::acronym_word_ranges(segment) {
+                parts.push(offset + part.start..offset + part.end);
+            }
+            // skip the separator.
+            offset += segment.len() + 1;
+        }
+        parts
+    }
+
+    /// Split a word into parts where identifiers seem glued together,
+    /// return the ranges of parts in the word.
+    ///
+    /// Without hints, split before each uppercase letter following
+    /// a lowercase one, e.g. "getUser" -> "get", "User".
+    /// With hints, split before each hint following a lowercase letter
+    /// or a digit, e.g. "getUserNamesetPassword" with hint "set"
+    /// -> "getUserName", "setPassword".
+    fn split_glued(word: &str, hints: &[String]) -> Vec<Range<usize>> {
+        let chars: Vec<(usize, char)> = word.char_indices().collect();
+        let mut boundaries = vec![0];
+        let mut i = 1;
+        while i < chars.len() {
+            let previous = chars[i - 1].1;
+            let (offset, current) = chars[i];
+            if hints.is_empty() {
+                if previous.is_lowercase() && current.is_uppercase() {
+                    boundaries.push(offset);
+                }
+            } else if previous.is_lowercase() || previous.is_numeric() {
+                let hint =
+                    hints.iter().find(|hint| word[offset..].starts_with(*hint));
+                if let Some(hint) = hint {
+                    boundaries.push(offset);
+                    // don't split inside the hint itself.
+                    i += hint.chars().count();
+                    continue;
+                }
+            }
+            i += 1;
+        }
+        boundaries.push(word.len());
+        boundaries.windows(2).map(|pair| pair[0]..pair[1]).collect()
+    }
+
+    /// Words captured by one pattern, with spans shifted by `offset`.
+    fn captures_of(
+        pattern: &Regex,
+        text: &str,
+        offset: usize,
+    ) -> Vec<(Range<usize>, String)> {
+        pattern
+            .captures_iter(text)
+            .filter_map(|cap| {
+                // the first group is the word, or the whole match
+                // for custom patterns without groups.
+                let cap = cap.unwrap();
+                let word = cap.get(1).or_else(|| cap.get(0))?;
+                if word.as_str().is_empty() {
+                    return None;
+                }
+                let span = offset + word.start()..offset + word.end();
+                Some((span, word.as_str().to_string()))
+            })
+            .collect()
+    }
+
+    /// Rewrite captured words in given text with the `replace` function,
+    /// words for which it returns `None` are kept untouched,
+    /// so as the rest of the text (punctuation, whitespace...).
+    pub fn replace_words<F>(&self, text: &str, mut replace: F) -> String
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let mut result = String::with_capacity(text.len());
+        let mut last_end = 0;
+        for (span, word) in self.get_sorted_spans_from(text) {
+            if let Some(replacement) = replace(&word) {
+                result.push_str(&text[last_end..span.start]);
+                result.push_str(&replacement);
+                last_end = span.end;
+            }
+        }
+        result.push_str(&text[last_end..]);
+        result
+    }
+
+    /// Sort captured words by position. When matches overlap, the first one
+    /// (for the same position, the one from the former pattern) wins.
+    fn get_sorted_spans_from(&self, text: &str) -> Vec<(Range<usize>, String)> {
+        let mut spans = self.capture_words_with_spans(text);
+        // stable sort, keeps the order of patterns for same positions.
+        spans.sort_by_key(|(span, _)| span.start);
+
+        let mut last_end = 0;
+        spans.retain(|(span, _)| {
+            let not_overlapping = span.start >= last_end;
+            if not_overlapping {
+                last_end = span.end;
+            }
+            not_overlapping
+        });
+        spans
+    }
+}
+
+#[cfg(test)]
+mod json_input_tests {
+    use super::{origins_from_json, NamingError};
+
+    #[test]
+    fn read_origins_from_json_outputs() {
+        let text =
+            r#"{"result":[{"origin":"pageSize","snake":"page_size"},"-a_"]}"#;
+        assert_eq!(origins_from_json(text).unwrap(), "pageSize\n-a_");
+
+        let pretty = "{\n  \"result\": [\n    {\n      \"origin\": \"a\\\"b\"\n    }\n  ]\n}";
+        assert_eq!(origins_from_json(pretty).unwrap(), "a\"b");
+    }
+
+    #[test]
+    fn malformed_json_input_is_an_error() {
+        for text in [r#"{"result":"#, r#"{"counts":{}}"#, r#"{"result":[1]}"#] {
+            let err = origins_from_json(text).unwrap_err();
+            assert!(matches!(err, NamingError::InvalidJsonInput(_)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod abbreviations_tests {
+    use super::parse_abbreviations;
+
+    #[test]
+    fn parse_abbreviation_lines() {
+        let text = "# team rules\nConfig = cfg\n\nmessage=MSG\n";
+        let abbreviations = parse_abbreviations(text, "a.txt").unwrap();
+        assert_eq!(abbreviations.len(), 2);
+        assert_eq!(abbreviations["config"], "cfg");
+        assert_eq!(abbreviations["message"], "msg");
+
+        let err = parse_abbreviations("a=b\nc_d=e", "a.txt").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "naming: a.txt: line 2: expect `word=abbreviation`, \
+            both are letters or digits."
+        );
+    }
+}
+
+#[cfg(test)]
+mod dir_walker_tests {
+    use std::fs;
+
+    use super::{glob_matches, DirWalker};
+    use crate::to_string_vec;
+
+    #[test]
+    fn match_globs() {
+        assert!(glob_matches("*.rs", "main.rs", "src/main.rs"));
+        assert!(!glob_matches("*.rs", "main.rsx", "src/main.rsx"));
+        assert!(glob_matches("ma?n.*", "main.rs", "main.rs"));
+        assert!(glob_matches("src/*.rs", "main.rs", "src/main.rs"));
+        assert!(!glob_matches("*.rs", "a", "src/a"));
+        assert!(!glob_matches("src/*.rs", "b.rs", "src/a/b.rs"));
+        assert!(glob_matches("src/**.rs", "b.rs", "src/a/b.rs"));
+    }
+
+    #[test]
+    fn walk_directories_with_filters() {
+        let root = std::env::temp_dir()
+            .join(format!("naming-walker-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        for file in ["x.rs", "y.txt", "a/z.rs", "a/b/w.rs", "target/t.rs"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&root, root.join("a/loop")).unwrap();
+
+        let root_str = root.to_string_lossy().to_string();
+        let walk = |max_depth| {
+            DirWalker::new(
+                Some(to_string_vec(vec!["*.rs"])),
+                Some(to_string_vec(vec!["target"])),
+                max_depth,
+            )
+            .expand(&[&root_str])
+            .unwrap()
+            .iter()
+            .map(|file| file[root_str.len() + 1..].to_string())
+            .collect::<Vec<String>>()
+        };
+        assert_eq!(walk(None), vec!["a/b/w.rs", "a/z.rs", "x.rs"]);
+        assert_eq!(walk(Some(2)), vec!["a/z.rs", "x.rs"]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
 
 #[cfg(test)]
 mod stdin_reader_tests {
-    use crate::extraction::read_from_input;
+    use std::fs;
+
+    use crate::extraction::{
+        read_file_list, read_from_input, read_nul_delimited_from_input,
+        split_file_list,
+    };
+    use crate::to_string_vec;
 
     #[test]
     fn read_from_input_without_logical_eof() {
@@ -143,6 +1281,49 @@ mod stdin_reader_tests {
         let actual = read_from_input(&input[..], Some("not-exists-eof"));
         assert_eq!(actual.unwrap(), "before-a end after-a");
     }
+
+    #[test]
+    fn normalize_line_endings_of_input() {
+        let input = b"pageSize\r\nuserId\rmax_size\r\n";
+        let actual = read_from_input(&input[..], None);
+        assert_eq!(actual.unwrap(), "pageSize\nuserId\nmax_size\n");
+    }
+
+    #[test]
+    fn split_nul_delimited_input_into_records() {
+        let input = b"first record\0second\0\0third\0";
+        let actual = read_nul_delimited_from_input(&input[..], None);
+        assert_eq!(
+            actual.unwrap(),
+            to_string_vec(vec!["first record", "second", "third"])
+        );
+    }
+
+    #[test]
+    fn apply_logical_eof_before_splitting_nul_delimited_input() {
+        let input = b"first\0second end\0third";
+        let actual = read_nul_delimited_from_input(&input[..], Some("end"));
+        assert_eq!(actual.unwrap(), to_string_vec(vec!["first", "second "]));
+    }
+
+    #[test]
+    fn split_file_list_by_lines_or_nul() {
+        let expect = to_string_vec(vec!["a.rs", "dir/b c.rs"]);
+        assert_eq!(split_file_list("a.rs\n\ndir/b c.rs\n", false), expect);
+        assert_eq!(split_file_list("a.rs\0dir/b c.rs\0", true), expect);
+    }
+
+    #[test]
+    fn missing_path_in_file_list_is_an_error() {
+        let list = std::env::temp_dir()
+            .join(format!("naming-file-list-test-{}", std::process::id()));
+        fs::write(&list, "not-exists/a.rs\n").unwrap();
+
+        let actual = read_file_list(list.to_str().unwrap(), false);
+        fs::remove_file(&list).unwrap();
+
+        assert!(actual.unwrap_err().to_string().contains("not-exists/a.rs"));
+    }
 }
 
 #[cfg(test)]
@@ -153,7 +1334,8 @@ mod captor_tests {
 
     #[test]
     fn return_empty_vec_when_no_match() {
-        let text = to_string_vec(vec!["不能被匹配"]);
+        // letters of any language are captured, punctuation isn't.
+        let text = to_string_vec(vec!["「」，。！"]);
         let actual = Captor::new(None).unwrap().capture_words(text);
         assert_eq!(actual, Vec::<String>::new())
     }
@@ -200,6 +1382,254 @@ mod captor_tests {
         assert_eq!(actual, expect);
     }
 
+    #[test]
+    fn overlapping_spans_are_taken_from_former_locator() {
+        let text = to_string_vec(vec![r#"let name = "user_name";"#]);
+        let locators = to_string_vec(vec![
+            "string-literal",
+            r"regex:(?<=\s)\S+(?=;)",
+            r"(?<=\b){}(?=\b)",
+        ]);
+        let actual = Captor::new(Some(locators)).unwrap().capture_words(text);
+        // `"user_name"` overlaps the literal content, and so does
+        // `user_name` from the default locator (deduplicated anyway),
+        // while `let` and `name` don't overlap with anything.
+        let expect = to_string_vec(vec!["user_name", "let", "name"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn capture_words_with_spans_on_multiple_lines() {
+        let text = "first line\nsecond_line\r\nthird";
+        let actual = Captor::new(None).unwrap().capture_words_with_spans(text);
+        let expect = vec![
+            (0..5, "first".to_string()),
+            (6..10, "line".to_string()),
+            (11..22, "second_line".to_string()),
+            (24..29, "third".to_string()),
+        ];
+        assert_eq!(actual, expect);
+        for (span, word) in actual {
+            assert_eq!(&text[span], word);
+        }
+    }
+
+    #[test]
+    fn replace_words_keeps_rest_of_text() {
+        let text = "let fooBar = baz(x);\n";
+        let actual = Captor::new(None).unwrap().replace_words(text, |word| {
+            if word == "let" {
+                None
+            } else {
+                Some(word.to_uppercase())
+            }
+        });
+        assert_eq!(actual, "let FOOBAR = BAZ(X);\n");
+    }
+
+    #[test]
+    fn replace_words_skips_overlapping_matches() {
+        let text = "abc";
+        let locators = to_string_vec(vec![r"a{}", r"\A{}"]);
+        let actual = Captor::new(Some(locators))
+            .unwrap()
+            .replace_words(text, |word| Some(format!("<{}>", word)));
+        assert_eq!(actual, "<abc>");
+    }
+
+    #[test]
+    fn custom_regex_locator_works() {
+        let text = to_string_vec(vec!["$userName = valid?(item_id);"]);
+        let locators = to_string_vec(vec![r"regex:\$\w+", r"regex:(\w+)\?\("]);
+        let actual = Captor::new(Some(locators)).unwrap().capture_words(text);
+        assert_eq!(actual, to_string_vec(vec!["$userName", "valid"]));
+    }
+
+    #[test]
+    fn invalid_regex_locator_is_an_error() {
+        let locators = to_string_vec(vec![r"regex:(\w+"]);
+        let err = Captor::new(Some(locators)).err().unwrap();
+        assert!(err.to_string().contains("regex:(\\w+"));
+        assert!(Captor::new(Some(to_string_vec(vec!["({}"]))).is_err());
+    }
+
+    #[test]
+    fn string_literal_locator_works() {
+        let text = r#"t("user_name", 'page-size'); "a\"quoted_key" + codeName"#;
+        let captor =
+            Captor::new(Some(to_string_vec(vec!["string-literal"]))).unwrap();
+        let actual = captor.capture_words(vec![text.to_string()]);
+        let expect =
+            to_string_vec(vec!["user_name", "page-size", "a", "quoted_key"]);
+        assert_eq!(actual, expect);
+        for (span, word) in captor.capture_words_with_spans(text) {
+            assert_eq!(&text[span], word);
+        }
+    }
+
+    #[test]
+    fn split_glued_locator_works() {
+        let text = to_string_vec(vec!["getUserNamesetPassword"]);
+        let captor = Captor::new(Some(to_string_vec(vec!["split-glued"])));
+        let actual = captor.unwrap().capture_words(text.clone());
+        let expect = to_string_vec(vec!["get", "User", "Nameset", "Password"]);
+        assert_eq!(actual, expect);
+
+        let locators = to_string_vec(vec!["split-glued:set|get"]);
+        let actual = Captor::new(Some(locators)).unwrap().capture_words(text);
+        let expect = to_string_vec(vec!["getUserName", "setPassword"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn split_digits_locator_works() {
+        let text = to_string_vec(vec!["config2json3yaml md5 2fa utf8_string"]);
+        let captor = Captor::new(Some(to_string_vec(vec!["split-digits"])));
+        let actual = captor.unwrap().capture_words(text);
+        let expect = to_string_vec(vec![
+            "config", "json", "yaml", "md", "fa", "utf", "string",
+        ]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn skip_comments_locator_works() {
+        let text = to_string_vec(vec![
+            r#"let url = "http://a_b"; // c_d
+/* e_f
+   g_h */ fn i_j<'k>(c: char) { '"' } /* unclosed l_m"#,
+        ]);
+        let captor =
+            Captor::new(Some(to_string_vec(vec!["skip-comments:rust"])));
+        let actual = captor.unwrap().capture_words(text);
+        let expect = to_string_vec(vec![
+            "let", "url", "http", "a_b", "fn", "i_j", "k", "c", "char",
+        ]);
+        assert_eq!(actual, expect);
+
+        let text = to_string_vec(vec![
+            "echo \"#fff\" $# ${#list[@]} # a_b\nprint('c#d') #e_f",
+        ]);
+        let captor =
+            Captor::new(Some(to_string_vec(vec!["skip-comments:shell"])));
+        let actual = captor.unwrap().capture_words(text);
+        let expect =
+            to_string_vec(vec!["echo", "fff", "list", "print", "c", "d"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn unknown_comment_language_is_an_error() {
+        let locators = Some(to_string_vec(vec!["skip-comments:cobol"]));
+        let err = Captor::new(locators).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "naming: locator `skip-comments:cobol`: unknown language `cobol`, \
+            valid languages: c,cpp,java,js,go,rust,shell,python,ruby."
+        );
+    }
+
+    #[cfg(feature = "normalize")]
+    #[test]
+    fn normalize_text_before_capturing() {
+        let text = || to_string_vec(vec!["ｆｏｏBar ｕｓｅｒ＿ｉｄ"]);
+        let captor = || Captor::new(None).unwrap();
+        // no word boundary around full-width letters.
+        assert!(captor().capture_words(text()).is_empty());
+        assert_eq!(
+            captor().with_normalization(true).capture_words(text()),
+            to_string_vec(vec!["fooBar", "user_id"])
+        );
+    }
+
+    #[test]
+    fn mixed_split_locator_works() {
+        let split = |word: &str| {
+            let text = to_string_vec(vec![word]);
+            let captor = Captor::new(Some(to_string_vec(vec!["mixed-split"])));
+            captor.unwrap().capture_words(text)
+        };
+        let cases = [
+            ("XMLData_parser-v2", vec!["XML", "Data", "parser", "v2"]),
+            ("get_HTTPResponse-code", vec!["get", "HTTP", "Response", "code"]),
+            ("__init__Value", vec!["init", "Value"]),
+            (
+                "snake_case-With-camelCase",
+                vec!["snake", "case", "With", "camel", "Case"],
+            ),
+            ("md5Sum_IO2Buffer", vec!["md5", "Sum", "IO2", "Buffer"]),
+            ("a_B-c", vec!["a", "B", "c"]),
+            ("ID", vec!["ID"]),
+        ];
+        for (word, expect) in cases {
+            assert_eq!(split(word), to_string_vec(expect));
+        }
+    }
+
+    #[test]
+    fn qualified_locator_works() {
+        let text = to_string_vec(vec!["obj.method(ns::Type, 3.14); end."]);
+        let captor = Captor::new(Some(to_string_vec(vec!["qualified"])));
+        let actual = captor.unwrap().capture_words(text);
+        let expect =
+            to_string_vec(vec!["obj.method", "ns::Type", "3", "14", "end"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn path_locator_works() {
+        let text = to_string_vec(vec!["open(user/profile/page, 3/4);"]);
+        let captor = Captor::new(Some(to_string_vec(vec!["path"])));
+        let actual = captor.unwrap().capture_words(text);
+        let expect = to_string_vec(vec!["open", "user/profile/page", "3/4"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn capture_words_in_markdown_code() {
+        let text = vec!["\
+The pageSize of `userId` and ``a `backTick` b``.
+```rust title
+let max_size = 1;
+```
+~~~
+in-tilde
+~~~
+after `unclosed
+````
+nested ```
+open_to_end"
+            .to_string()];
+        let locators = to_string_vec(vec!["markdown-code"]);
+        let actual = Captor::new(Some(locators)).unwrap().capture_words(text);
+        let expect = to_string_vec(vec![
+            "userId",
+            "a",
+            "backTick",
+            "b",
+            "let",
+            "max_size",
+            "1",
+            "in-tilde",
+            "nested",
+            "open_to_end",
+        ]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn capture_words_in_parallel_keeps_order() {
+        let text = to_string_vec(vec!["a b", "c a", "d"]);
+        let captor = Captor::new(None).unwrap();
+        let expect = captor.capture_words(text.clone());
+        for jobs in 1..4 {
+            assert_eq!(
+                captor.capture_words_in_parallel(text.clone(), jobs).unwrap(),
+                expect
+            );
+        }
+    }
+
     #[test]
     fn can_capture_all_formats() {
         let text = to_string_vec(vec![
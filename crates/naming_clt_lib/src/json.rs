@@ -0,0 +1,218 @@
+// A minimal json parser, only for reading back this tool's own
+// json outputs (see `origins_from_json`), not a general purpose one.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+// arrays and objects nested deeper are rejected,
+// instead of overflowing the stack by recursion.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    // numbers are kept as their literal text, nobody needs their value.
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    // keep the fields order.
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    pub(crate) fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the whole text as one json value,
+/// surrounding whitespace is allowed.
+pub(crate) fn parse(text: &str) -> Result<JsonValue, String> {
+    let mut chars = text.chars().peekable();
+    let value = parse_value(&mut chars, 0)?;
+    skip_whitespace(&mut chars);
+    match chars.next() {
+        None => Ok(value),
+        Some(c) => Err(format!("unexpected `{}` after json value", c)),
+    }
+}
+
+// `depth` is the number of arrays and objects around the value.
+fn parse_value(
+    chars: &mut Peekable<Chars>,
+    depth: usize,
+) -> Result<JsonValue, String> {
+    skip_whitespace(chars);
+    match chars.peek() {
+        Some('{') | Some('[') if depth >= MAX_DEPTH => {
+            Err("nesting too deep".to_string())
+        }
+        Some('{') => parse_object(chars, depth + 1),
+        Some('[') => parse_array(chars, depth + 1),
+        Some('"') => Ok(JsonValue::String(parse_string(chars)?)),
+        Some(c) if *c == '-' || c.is_ascii_digit() => {
+            let mut number = String::new();
+            while let Some(c) =
+                chars.next_if(|c| c.is_ascii_digit() || "+-.eE".contains(*c))
+            {
+                number.push(c);
+            }
+            Ok(JsonValue::Number(number))
+        }
+        Some(&first) => {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+                word.push(c);
+            }
+            match word.as_str() {
+                "null" => Ok(JsonValue::Null),
+                "true" => Ok(JsonValue::Bool(true)),
+                "false" => Ok(JsonValue::Bool(false)),
+                "" => Err(format!("unexpected `{}`", first)),
+                _ => Err(format!("unexpected `{}`", word)),
+            }
+        }
+        None => Err("unexpected end of json".to_string()),
+    }
+}
+
+fn parse_object(
+    chars: &mut Peekable<Chars>,
+    depth: usize,
+) -> Result<JsonValue, String> {
+    chars.next();
+    let mut fields = Vec::new();
+    skip_whitespace(chars);
+    if chars.next_if_eq(&'}').is_some() {
+        return Ok(JsonValue::Object(fields));
+    }
+    loop {
+        skip_whitespace(chars);
+        if chars.peek() != Some(&'"') {
+            return Err("expect a string as object key".to_string());
+        }
+        let key = parse_string(chars)?;
+        skip_whitespace(chars);
+        if chars.next() != Some(':') {
+            return Err("expect `:` after object key".to_string());
+        }
+        fields.push((key, parse_value(chars, depth)?));
+        skip_whitespace(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => return Ok(JsonValue::Object(fields)),
+            _ => return Err("expect `,` or `}` in object".to_string()),
+        }
+    }
+}
+
+fn parse_array(
+    chars: &mut Peekable<Chars>,
+    depth: usize,
+) -> Result<JsonValue, String> {
+    chars.next();
+    let mut values = Vec::new();
+    skip_whitespace(chars);
+    if chars.next_if_eq(&']').is_some() {
+        return Ok(JsonValue::Array(values));
+    }
+    loop {
+        values.push(parse_value(chars, depth)?);
+        skip_whitespace(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some(']') => return Ok(JsonValue::Array(values)),
+            _ => return Err("expect `,` or `]` in array".to_string()),
+        }
+    }
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    chars.next();
+    let mut string = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Ok(string),
+            '\\' => match chars.next() {
+                Some('"') => string.push('"'),
+                Some('\\') => string.push('\\'),
+                Some('/') => string.push('/'),
+                Some('b') => string.push('\u{8}'),
+                Some('f') => string.push('\u{c}'),
+                Some('n') => string.push('\n'),
+                Some('r') => string.push('\r'),
+                Some('t') => string.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| {
+                            format!("invalid escape `\\u{}`", hex)
+                        })?;
+                    string.push(c);
+                }
+                _ => return Err("invalid escape in string".to_string()),
+            },
+            c => string.push(c),
+        }
+    }
+    Err("unclosed string".to_string())
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+#[cfg(test)]
+mod json_tests {
+    use super::{parse, JsonValue};
+
+    #[test]
+    fn parse_nested_values() {
+        let text = r#" {"a":[1, -2.5e3, "x\"A"], "b": {}, "c": [true, null]} "#;
+        let expect = JsonValue::Object(vec![
+            (
+                "a".to_string(),
+                JsonValue::Array(vec![
+                    JsonValue::Number("1".to_string()),
+                    JsonValue::Number("-2.5e3".to_string()),
+                    JsonValue::String("x\"A".to_string()),
+                ]),
+            ),
+            ("b".to_string(), JsonValue::Object(vec![])),
+            (
+                "c".to_string(),
+                JsonValue::Array(vec![JsonValue::Bool(true), JsonValue::Null]),
+            ),
+        ]);
+        assert_eq!(parse(text), Ok(expect));
+    }
+
+    #[test]
+    fn malformed_json_is_an_error() {
+        assert!(parse(r#"{"a":1"#).is_err());
+        assert!(parse(r#"{"a" 1}"#).is_err());
+        assert!(parse(r#"["a",]"#).is_err());
+        assert!(parse(r#""a" "b""#).is_err());
+        assert!(parse("nope").is_err());
+    }
+
+    #[test]
+    fn deeply_nested_json_is_an_error() {
+        let text = "[".repeat(100_000);
+        assert_eq!(parse(&text), Err("nesting too deep".to_string()));
+
+        let text = "[".repeat(128) + &"]".repeat(128);
+        assert!(parse(&text).is_ok());
+        let text = "{\"a\":".repeat(129) + "1" + &"}".repeat(129);
+        assert!(parse(&text).is_err());
+    }
+}
@@ -1,60 +1,50 @@
 #[macro_use]
 extern crate lazy_static;
 
-use std::collections::HashMap;
-
+pub use cases::*;
 pub use conversion::*;
+pub use error::*;
 pub use extraction::*;
-use naming_lib::NamingCase;
+pub use registry::*;
 
+mod cases;
 mod conversion;
+mod error;
 mod extraction;
-
-/// Intermediate type for converting
-/// NamingCase type to String in different output format.
-struct Formatter {
-    pub inner: fn(&NamingCase) -> String,
-}
-
-impl Clone for Formatter {
-    fn clone(&self) -> Self {
-        Formatter { inner: self.inner }
-    }
-}
-
-impl Copy for Formatter {}
+mod json;
+mod registry;
 
 lazy_static! {
     // default option of `--filter` and `--output`, i.e. allow all formats.
     static ref DEFAULT_OPTIONS: Vec<String> = to_string_vec(vec!["S", "s", "k", "c", "p"]);
+}
 
-    // used for converting NamingCase to String type in iteration.
-    static ref DIRECT_MAPPERS: HashMap<&'static str, Formatter> = {
-        let mut map: HashMap<&'static str, Formatter> = HashMap::new();
-        map.insert("S", Formatter{inner:|case| case.to_screaming_snake().unwrap()});
-        map.insert("s", Formatter{inner:|case| case.to_snake().unwrap()});
-        map.insert("k", Formatter{inner:|case| case.to_kebab().unwrap()});
-        map.insert("c", Formatter{inner:|case| case.to_camel().unwrap()});
-        map.insert("p", Formatter{inner:|case| case.to_pascal().unwrap()});
-        map
-    };
+/// Wrap the value in double quotes so that it is always
+/// a valid YAML string scalar (e.g. "-a" or "a: b").
+fn to_yaml_string(value: &str) -> String {
+    "\"".to_string() + &value.replace('\\', "\\\\").replace('"', "\\\"") + "\""
+}
 
-    // same as above.
-    static ref JSON_MAPPERS:HashMap<&'static str, Formatter> = {
-        fn compose(key:&str,value:String) -> String {
-              "\"".to_string() + key + "\":\"" + &value + "\""
+/// Wrap the value in double quotes as a JSON string, escaping
+/// double quotes, backslashes and control characters per RFC 8259.
+fn to_json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                escaped.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => escaped.push(c),
         }
-
-        let mut map: HashMap<&'static str, Formatter> = HashMap::new();
-        map.insert("S", Formatter{
-            inner:|case| compose("screaming_snake",case.to_screaming_snake().unwrap())
-        });
-        map.insert("s", Formatter{inner:|case| compose("snake",case.to_snake().unwrap())});
-        map.insert("k", Formatter{inner:|case| compose("kebab",case.to_kebab().unwrap())});
-        map.insert("c", Formatter{inner:|case| compose("camel",case.to_camel().unwrap())});
-        map.insert("p", Formatter{inner:|case| compose("pascal",case.to_pascal().unwrap())});
-        map
-    };
+    }
+    escaped.push('"');
+    escaped
 }
 
 pub fn to_string_vec(ori: Vec<&str>) -> Vec<String> {
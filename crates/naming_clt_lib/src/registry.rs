@@ -0,0 +1,385 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use naming_lib::NamingCase;
+
+use super::{AcronymStyle, CaseSpec, NamingCaseKind};
+
+/// Signature of conversion functions, takes the case to convert
+/// and the set of known (uppercase) acronyms.
+pub type ConversionFn =
+    fn(&NamingCase, &HashSet<String>) -> Result<String, &'static str>;
+
+/// An output format that carries its own state (e.g. acronyms,
+/// separators), so that it needs nothing but the case to convert,
+/// register it with `ConversionRegistry::with_mapper`.
+///
+/// `Format` implements it, with every option of the `Convertor`
+/// (affixes, acronyms...) applied. Built-in formats are `Format` values
+/// in the default `ConversionRegistry` rather than unit structs,
+/// as a unit struct can't carry these options.
+pub trait CaseMapper {
+    /// Used both as the `--output` value and as the name
+    /// in structured outputs, e.g. "screaming_snake".
+    fn key(&self) -> &str;
+
+    fn map(&self, case: &NamingCase) -> Result<String, &'static str>;
+}
+
+#[derive(Clone)]
+enum Conversion {
+    Function(ConversionFn),
+    Spec(CaseSpec),
+    Mapper(Arc<dyn CaseMapper + Send + Sync>),
+}
+
+/// An output format in `ConversionRegistry`,
+/// or a custom one described by a `CaseSpec`.
+#[derive(Clone)]
+pub struct Format {
+    name: String,
+    conversion: Conversion,
+    // added around converted strings, see `Convertor::with_affixes`.
+    prefix: String,
+    suffix: String,
+    // only built-in formats that capitalize words follow the style.
+    capitalizes_words: bool,
+    acronym_style: AcronymStyle,
+    // lowercase word -> its abbreviation, see `Convertor::with_abbreviations`.
+    abbreviations: HashMap<String, String>,
+    // see `Convertor::with_acronyms_merged`.
+    merge_acronyms: bool,
+    // (uppercase) acronyms passed to conversion functions
+    // when converting as a `CaseMapper`.
+    acronyms: HashSet<String>,
+}
+
+impl CaseMapper for Format {
+    fn key(&self) -> &str {
+        &self.name
+    }
+
+    fn map(&self, case: &NamingCase) -> Result<String, &'static str> {
+        self.convert(case, &self.acronyms)
+    }
+}
+
+impl Format {
+    /// The name of this format, used as keys or headers
+    /// in structured outputs, e.g. "screaming_snake".
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Convert given case to this format, with affixes around.
+    ///
+    /// A qualified name (see `split_qualified`, e.g. "obj.method") is
+    /// converted segment by segment, keeping the separators between them,
+    /// e.g. "myObj::getName" -> "my_obj::get_name" in snake case.
+    /// A path/case word (see `from_path`, e.g. "user/profile") is
+    /// converted as the snake case word it stands for.
+    pub fn convert(
+        &self,
+        case: &NamingCase,
+        acronyms: &HashSet<String>,
+    ) -> Result<String, &'static str> {
+        // a qualified name is kept as an invalid case by `Filter`.
+        let qualified = match case {
+            NamingCase::Invalid(origin) => super::split_qualified(origin),
+            _ => None,
+        };
+        let converted = match qualified {
+            Some((segments, separators)) => {
+                let mut converted = String::new();
+                // no separator before the first segment.
+                let separators = std::iter::once("").chain(separators);
+                for (separator, segment) in separators.zip(segments) {
+                    let segment = super::which_case(segment);
+                    converted.push_str(separator);
+                    converted += &self.convert_segment(&segment, acronyms)?;
+                }
+                converted
+            }
+            None => {
+                // so is a path/case word, see `from_path`.
+                let path = match case {
+                    NamingCase::Invalid(origin) => super::from_path(origin),
+                    _ => None,
+                };
+                self.convert_segment(path.as_ref().unwrap_or(case), acronyms)?
+            }
+        };
+        if self.prefix.is_empty() && self.suffix.is_empty() {
+            return Ok(converted);
+        }
+        Ok(self.prefix.clone() + &converted + &self.suffix)
+    }
+
+    // convert a plain identifier, without affixes.
+    fn convert_segment(
+        &self,
+        case: &NamingCase,
+        acronyms: &HashSet<String>,
+    ) -> Result<String, &'static str> {
+        if !self.capitalizes_words
+            && self.abbreviations.is_empty()
+            && !self.merge_acronyms
+        {
+            return self.apply(case, acronyms);
+        }
+        // other formats don't render acronyms in any style.
+        let (case, acronyms) = if self.capitalizes_words {
+            super::with_acronym_style(case, acronyms, self.acronym_style)
+        } else {
+            (super::which_case(&case.to_string()), acronyms.clone())
+        };
+        let case = if self.merge_acronyms {
+            super::merge_acronyms(&case)
+        } else {
+            case
+        };
+        let case = super::abbreviate(&case, &self.abbreviations);
+        self.apply(&case, &acronyms)
+    }
+
+    fn apply(
+        &self,
+        case: &NamingCase,
+        acronyms: &HashSet<String>,
+    ) -> Result<String, &'static str> {
+        match &self.conversion {
+            Conversion::Function(function) => function(case, acronyms),
+            Conversion::Spec(spec) => super::to_custom(case, spec, acronyms),
+            // a mapper keeps its own acronyms.
+            Conversion::Mapper(mapper) => mapper.map(case),
+        }
+    }
+
+    /// A format described by given spec, named by the `--output` value
+    /// it's parsed from, e.g. "custom:-:upper".
+    pub fn custom(name: &str, spec: CaseSpec) -> Format {
+        let mut format = Format::new(name, Conversion::Spec(spec.clone()));
+        format.capitalizes_words = spec.capitalizes_words();
+        format
+    }
+
+    pub(crate) fn with_affixes(mut self, prefix: &str, suffix: &str) -> Format {
+        self.prefix = prefix.to_string();
+        self.suffix = suffix.to_string();
+        self
+    }
+
+    pub(crate) fn with_acronyms(
+        mut self,
+        acronyms: &HashSet<String>,
+    ) -> Format {
+        self.acronyms = acronyms.clone();
+        self
+    }
+
+    pub(crate) fn with_acronyms_merged(mut self, merge: bool) -> Format {
+        self.merge_acronyms = merge;
+        self
+    }
+
+    pub(crate) fn with_abbreviations(
+        mut self,
+        abbreviations: &HashMap<String, String>,
+    ) -> Format {
+        self.abbreviations = abbreviations.clone();
+        self
+    }
+
+    pub(crate) fn with_acronym_style(mut self, style: AcronymStyle) -> Format {
+        if self.capitalizes_words {
+            self.acronym_style = style;
+        }
+        self
+    }
+
+    fn new(name: &str, conversion: Conversion) -> Format {
+        Format {
+            name: name.to_string(),
+            conversion,
+            prefix: String::new(),
+            suffix: String::new(),
+            capitalizes_words: false,
+            acronym_style: AcronymStyle::Title,
+            abbreviations: HashMap::new(),
+            merge_acronyms: false,
+            acronyms: HashSet::new(),
+        }
+    }
+}
+
+/// Output formats that `Convertor` can convert cases to,
+/// keyed by values of `--output` option (e.g. "S").
+///
+/// The default registry holds the built-in formats (see `NamingCaseKind`),
+/// downstream users can register their own formats with `with_format`
+/// (or `with_mapper` for stateful ones, see `CaseMapper`)
+/// and pass the registry to `Convertor::with_registry`.
+#[derive(Clone)]
+pub struct ConversionRegistry {
+    // keep the registering order for listing.
+    formats: Vec<(String, Format)>,
+}
+
+impl Default for ConversionRegistry {
+    fn default() -> Self {
+        let builtin: [(NamingCaseKind, ConversionFn); 12] = [
+            (NamingCaseKind::ScreamingSnake, |case, _| {
+                super::to_screaming_snake(case)
+            }),
+            (NamingCaseKind::Snake, |case, _| super::to_snake(case)),
+            (NamingCaseKind::Kebab, |case, _| super::to_kebab(case)),
+            (NamingCaseKind::Camel, super::to_camel_with_acronyms),
+            (NamingCaseKind::Pascal, super::to_pascal_with_acronyms),
+            (NamingCaseKind::Train, super::to_train_with_acronyms),
+            (NamingCaseKind::Dot, |case, _| super::to_dot(case)),
+            (NamingCaseKind::Flat, |case, _| super::to_flat(case)),
+            (NamingCaseKind::UpperFlat, |case, _| super::to_upper_flat(case)),
+            (NamingCaseKind::Ada, super::to_ada_with_acronyms),
+            (NamingCaseKind::Cobol, |case, _| super::to_cobol(case)),
+            (NamingCaseKind::Path, |case, _| super::to_path(case)),
+        ];
+
+        let formats = builtin
+            .iter()
+            .map(|(kind, function)| {
+                let conversion = Conversion::Function(*function);
+                let mut format = Format::new(kind.name(), conversion);
+                format.capitalizes_words = matches!(
+                    kind,
+                    NamingCaseKind::Camel
+                        | NamingCaseKind::Pascal
+                        | NamingCaseKind::Train
+                        | NamingCaseKind::Ada
+                );
+                (kind.letter().to_string(), format)
+            })
+            .collect();
+        ConversionRegistry { formats }
+    }
+}
+
+impl ConversionRegistry {
+    /// Register a format, the key is used both as the `--output` value
+    /// and as the name in structured outputs.
+    /// A built-in format with the same key is replaced.
+    pub fn with_format(self, key: &str, function: ConversionFn) -> Self {
+        self.with(Format::new(key, Conversion::Function(function)))
+    }
+
+    /// Same as `with_format`, but register a stateful mapper,
+    /// keyed by its `CaseMapper::key`.
+    pub fn with_mapper<M>(self, mapper: M) -> Self
+    where
+        M: CaseMapper + Send + Sync + 'static,
+    {
+        let key = mapper.key().to_string();
+        self.with(Format::new(&key, Conversion::Mapper(Arc::new(mapper))))
+    }
+
+    fn with(mut self, format: Format) -> Self {
+        match self.formats.iter_mut().find(|(k, _)| *k == format.name) {
+            Some((_, existing)) => *existing = format,
+            None => self.formats.push((format.name.clone(), format)),
+        }
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Format> {
+        self.formats.iter().find(|(k, _)| k == key).map(|(_, format)| format)
+    }
+
+    /// All registered formats with their keys, in registering order.
+    pub fn formats(&self) -> impl Iterator<Item = (&str, &Format)> {
+        self.formats.iter().map(|(key, format)| (key.as_str(), format))
+    }
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use std::collections::HashSet;
+
+    use naming_lib::{which_case, NamingCase};
+
+    use super::{CaseMapper, ConversionRegistry};
+
+    #[test]
+    fn default_registry_has_builtin_formats() {
+        let registry = ConversionRegistry::default();
+        let format = registry.get("S").unwrap();
+        assert_eq!(format.name(), "screaming_snake");
+        assert_eq!(
+            format.convert(&which_case("pageSize"), &HashSet::new()),
+            Ok("PAGE_SIZE".to_string())
+        );
+        assert!(registry.get("x").is_none());
+    }
+
+    #[test]
+    fn list_formats_in_registering_order() {
+        let registry = ConversionRegistry::default()
+            .with_format("s", |case, _| case.to_kebab())
+            .with_format("x", |case, _| case.to_snake());
+        let keys: Vec<&str> = registry.formats().map(|(key, _)| key).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "S", "s", "k", "c", "p", "t", "d", "f", "F", "a", "C", "/", "x"
+            ]
+        );
+        // replaced in place.
+        assert_eq!(registry.get("s").unwrap().name(), "s");
+    }
+
+    #[test]
+    fn register_custom_format() {
+        let registry = ConversionRegistry::default()
+            .with_format("path", |case, _| {
+                case.to_snake().map(|s| s.replace('_', "/"))
+            });
+        let format = registry.get("path").unwrap();
+        assert_eq!(format.name(), "path");
+        assert_eq!(
+            format.convert(&which_case("pageSize"), &HashSet::new()),
+            Ok("page/size".to_string())
+        );
+    }
+
+    // a mapper with its own state, a separator.
+    struct Joined {
+        key: String,
+        separator: &'static str,
+    }
+
+    impl CaseMapper for Joined {
+        fn key(&self) -> &str {
+            &self.key
+        }
+
+        fn map(&self, case: &NamingCase) -> Result<String, &'static str> {
+            case.to_snake().map(|s| s.replace('_', self.separator))
+        }
+    }
+
+    #[test]
+    fn register_stateful_mapper() {
+        let registry = ConversionRegistry::default()
+            .with_mapper(Joined { key: "plus".to_string(), separator: "+" });
+        let format = registry.get("plus").unwrap();
+        assert_eq!(format.key(), "plus");
+        assert_eq!(
+            format.map(&which_case("pageSize")),
+            Ok("page+size".to_string())
+        );
+        // acronyms are up to the mapper.
+        let acronyms = vec!["SIZE".to_string()].into_iter().collect();
+        assert_eq!(
+            format.convert(&which_case("pageSize"), &acronyms),
+            Ok("page+size".to_string())
+        );
+    }
+}
@@ -43,3 +43,12 @@ fn apply_logical_eof_on_each_file_when_read_multiple_files() {
         lib::to_string_vec(vec!["front-a ", "front-b hold after-b"])
     );
 }
+
+#[test]
+fn normalize_crlf_line_endings_in_file() {
+    let text = lib::read_from_files(&["tests/data/crlf.txt"], None);
+    assert_eq!(
+        text.unwrap(),
+        lib::to_string_vec(vec!["front-c\nstop\nafter-c\n"])
+    );
+}
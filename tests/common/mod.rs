@@ -0,0 +1,9 @@
+use assert_cmd::Command;
+
+/// The binary under test, with the config file ignored, so that
+/// a developer's own naming.toml never changes the outputs.
+pub fn naming() -> Command {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--no-config");
+    cmd
+}
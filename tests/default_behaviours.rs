@@ -1,8 +1,10 @@
-use assert_cmd::Command;
+use common::naming;
+
+mod common;
 
 #[test]
 fn read_from_single_file() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("tests/data/all.txt").assert().success().stdout(
         "\
 SCREAMING_SNAKE SCREAMING_SNAKE screaming_snake screaming-snake screamingSnake ScreamingSnake
@@ -15,7 +17,7 @@ PascalCase PASCAL_CASE pascal_case pascal-case pascalCase PascalCase",
 
 #[test]
 fn read_from_multiple_files() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("tests/data/one.txt")
         .arg("tests/data/all.txt")
         .assert()
@@ -33,7 +35,7 @@ PascalCase PASCAL_CASE pascal_case pascal-case pascalCase PascalCase",
 
 #[test]
 fn read_from_stdin() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.write_stdin(
         "SCREAMING_SNAKE snake_case kebab-case camelCase PascalCase",
     )
@@ -1,15 +1,18 @@
-use assert_cmd::Command;
 use predicates::{str as it, Predicate};
 
+use common::naming;
+
+mod common;
+
 #[test]
 fn no_input() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.assert().success();
 }
 
 #[test]
 fn input_not_exist_file() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("not_exist.txt").assert().failure();
 
     let err_msg = cmd.output().unwrap().stderr;
@@ -19,7 +22,7 @@ fn input_not_exist_file() {
 
 #[test]
 fn filter_option_has_hungarian_camel_conflict() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("-f=c,h").assert().failure();
 
     let err_msg = cmd.output().unwrap().stderr;
@@ -29,7 +32,7 @@ fn filter_option_has_hungarian_camel_conflict() {
 
 #[test]
 fn wrong_locator_that_can_not_be_converted_to_regex() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg(r#"-l="a""#).assert().failure();
     cmd.arg(r#"-l="a ""#).assert().failure();
     cmd.arg(r#"-l=" a""#).assert().failure();
@@ -38,3 +41,180 @@ fn wrong_locator_that_can_not_be_converted_to_regex() {
     let err_msg = String::from_utf8_lossy(&err_msg);
     assert!(it::contains("locator").eval(&err_msg));
 }
+
+#[test]
+fn invalid_custom_regex_locator() {
+    let mut cmd = naming();
+    cmd.arg(r"--locator=regex:(\w+").arg("tests/data/one.txt");
+    cmd.assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("invalid regex").eval(&err_msg));
+}
+
+#[test]
+fn output_file_is_not_writable() {
+    let mut cmd = naming();
+    cmd.args(["--output-file", "tests/data/not-exists/out.txt"])
+        .arg("tests/data/one.txt");
+    cmd.assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("not-exists/out.txt").eval(&err_msg));
+}
+
+#[test]
+fn jobs_is_not_a_positive_integer() {
+    let mut cmd = naming();
+    cmd.args(["--jobs=0", "tests/data/one.txt"]).assert().failure();
+}
+
+#[test]
+fn binary_file_without_skipping() {
+    let mut cmd = naming();
+    cmd.args(["--no-skip-binary", "tests/data/binary.bin"]);
+    cmd.assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("tests/data/binary.bin").eval(&err_msg));
+}
+
+#[test]
+fn malformed_json_input() {
+    let mut cmd = naming();
+    cmd.arg("--input-format=json").write_stdin("pageSize").assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("not a json output").eval(&err_msg));
+}
+
+#[test]
+fn deeply_nested_json_input() {
+    let mut cmd = naming();
+    cmd.arg("--input-format=json")
+        .write_stdin("[".repeat(100_000))
+        .assert()
+        .failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("nesting too deep").eval(&err_msg));
+}
+
+#[test]
+fn invalid_limit() {
+    let mut cmd = naming();
+    cmd.arg("--limit=-1").write_stdin("aB").assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("limit").eval(&err_msg));
+}
+
+#[test]
+fn invalid_fuzzy_filter_tolerance() {
+    let mut cmd = naming();
+    cmd.arg("--fuzzy-filter=one").write_stdin("aB").assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("fuzzy-filter").eval(&err_msg));
+}
+
+#[cfg(not(feature = "sample"))]
+#[test]
+fn sample_without_feature() {
+    let mut cmd = naming();
+    cmd.arg("--sample=1").write_stdin("aB").assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("`sample` feature").eval(&err_msg));
+}
+
+#[cfg(not(feature = "normalize"))]
+#[test]
+fn normalize_without_feature() {
+    let mut cmd = naming();
+    cmd.arg("--normalize").write_stdin("aB").assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("`normalize` feature").eval(&err_msg));
+}
+
+#[test]
+fn invalid_output_format() {
+    let mut cmd = naming();
+    cmd.arg("--output=x").write_stdin("aB").assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("unknown output format `x`").eval(&err_msg));
+
+    let mut cmd = naming();
+    cmd.arg("--output=custom:-:camel").write_stdin("aB").assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("word case: lower, upper or title").eval(&err_msg));
+}
+
+#[test]
+fn invalid_regex_wrap_width() {
+    let mut cmd = naming();
+    cmd.args(["--regex", "--wrap=0"]).write_stdin("aB").assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("wrap").eval(&err_msg));
+}
+
+#[test]
+fn missing_path_in_files_from_list() {
+    let mut cmd = naming();
+    cmd.args(["--files-from", "-"])
+        .write_stdin("tests/data/one.txt\nnot-exists/a.rs\n")
+        .assert()
+        .failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("not-exists/a.rs").eval(&err_msg));
+}
+
+#[test]
+fn unknown_key_in_config_file() {
+    let dir = std::env::temp_dir().join("naming_config_unknown_key_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("naming.toml"), "sorted = true\n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("naming").unwrap();
+    cmd.current_dir(&dir).env("XDG_CONFIG_HOME", &dir);
+    cmd.write_stdin("aB").assert().failure();
+
+    let err_msg = cmd.output().unwrap().stderr;
+    let err_msg = String::from_utf8_lossy(&err_msg);
+    assert!(it::contains("naming.toml: unknown key `sorted`").eval(&err_msg));
+}
+
+#[test]
+fn unknown_format_of_replace_and_sort_by() {
+    let expected = [
+        ("--replace=x", "`x`: unknown naming case"),
+        ("--sort-by=x", "unknown output format `x`"),
+        ("--sort-by=custom:", "output `custom:`: should be custom:"),
+    ];
+    for (option, expected) in expected {
+        let mut cmd = naming();
+        cmd.arg(option).write_stdin("aB").assert().failure();
+
+        let err_msg = cmd.output().unwrap().stderr;
+        let err_msg = String::from_utf8_lossy(&err_msg);
+        assert!(it::contains(expected).eval(&err_msg));
+    }
+}
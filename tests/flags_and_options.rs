@@ -1,8 +1,12 @@
 use assert_cmd::Command;
 
+use common::naming;
+
+mod common;
+
 #[test]
 fn filter() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("--filter=c,s")
         .arg("tests/data/all.txt")
         .assert()
@@ -16,7 +20,7 @@ camelCase CAMEL_CASE camel_case camel-case camelCase CamelCase",
 
 #[test]
 fn filter_hungarian_option() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("--filter=h")
         .arg("tests/data/all.txt")
         .assert()
@@ -24,9 +28,20 @@ fn filter_hungarian_option() {
         .stdout("Case CASE case case case Case");
 }
 
+#[test]
+fn filter_out() {
+    let mut cmd = naming();
+    cmd.arg("--filter-out=s,S,k")
+        .arg("--output=s")
+        .arg("tests/data/all.txt")
+        .assert()
+        .success()
+        .stdout("camelCase camel_case\nPascalCase pascal_case");
+}
+
 #[test]
 fn eof() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     // set part of second word as logical eof
     cmd.arg("--eof=snake")
         .arg("tests/data/all.txt")
@@ -41,7 +56,7 @@ SCREAMING_SNAKE SCREAMING_SNAKE screaming_snake screaming-snake screamingSnake S
 
 #[test]
 fn locator() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg(r"--locator=\scam{}Case\s,ke{}-case")
         .arg("tests/data/all.txt")
         .assert()
@@ -49,9 +64,63 @@ fn locator() {
         .stdout("el EL el el el El\nbab BAB bab bab bab Bab");
 }
 
+#[test]
+fn custom_regex_locator() {
+    let mut cmd = naming();
+    cmd.arg(r"--locator=regex:(?m)^(\w+)_case$")
+        .arg("tests/data/all.txt")
+        .assert()
+        .success()
+        .stdout("snake SNAKE snake snake snake Snake");
+}
+
+#[test]
+fn string_literal_locator() {
+    let mut cmd = naming();
+    cmd.arg("--locator=string-literal")
+        .arg("--output=s")
+        .write_stdin(r#"let userName = t("pageSize");"#)
+        .assert()
+        .success()
+        .stdout("pageSize page_size");
+}
+
+#[test]
+fn markdown_code_locator() {
+    let mut cmd = naming();
+    cmd.arg("--locator=markdown-code")
+        .arg("--output=s")
+        .write_stdin("Set the `pageSize` option:\n```js\nuserName\n```\n")
+        .assert()
+        .success()
+        .stdout("pageSize page_size\nuserName user_name");
+}
+
+#[test]
+fn combined_locators_drop_overlapping_matches() {
+    let mut cmd = naming();
+    cmd.arg(r"--locator=string-literal,regex:\((\S+)\)")
+        .arg("--output=s")
+        .write_stdin(r#"t("pageSize") + f(userName)"#)
+        .assert()
+        .success()
+        .stdout("pageSize page_size\nuserName user_name");
+}
+
+#[test]
+fn split_glued_locator() {
+    let mut cmd = naming();
+    cmd.arg("--locator=split-glued:set|get")
+        .arg("--output=s")
+        .write_stdin("getUserNamesetPassword")
+        .assert()
+        .success()
+        .stdout("getUserName get_user_name\nsetPassword set_password");
+}
+
 #[test]
 fn locator_support_lookaround() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg(r"--locator=(?<=u){}(?=Id)")
         .arg("tests/data/one.txt")
         .assert()
@@ -61,7 +130,7 @@ fn locator_support_lookaround() {
 
 #[test]
 fn output_option_order_affects_output_order() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("--output=c,s")
         .arg("tests/data/one.txt")
         .assert()
@@ -71,7 +140,7 @@ fn output_option_order_affects_output_order() {
 
 #[test]
 fn json_flag() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("--json").arg("tests/data/one.txt").assert().success().stdout(
         r#"{"result":[{"origin":"userId","#.to_string()
             + r#""screaming_snake":"USER_ID","snake":"user_id","#
@@ -82,7 +151,7 @@ fn json_flag() {
 
 #[test]
 fn regex_flag() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("--regex")
         .arg("tests/data/one.txt")
         .assert()
@@ -92,7 +161,7 @@ fn regex_flag() {
 
 #[test]
 fn json_regex() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("--json")
         .arg("--regex")
         .arg("tests/data/one.txt")
@@ -106,7 +175,7 @@ fn json_regex() {
 
 #[test]
 fn output_regex() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("--output=s,c")
         .arg("--regex")
         .arg("tests/data/one.txt")
@@ -117,7 +186,7 @@ fn output_regex() {
 
 #[test]
 fn output_json() {
-    let mut cmd = Command::cargo_bin("naming").unwrap();
+    let mut cmd = naming();
     cmd.arg("--output=k,S")
         .arg("--json")
         .arg("tests/data/one.txt")
@@ -128,3 +197,1132 @@ fn output_json() {
                 + r#""kebab":"user-id","screaming_snake":"USER_ID"}]}"#,
         );
 }
+
+#[test]
+fn output_train_case() {
+    let mut cmd = naming();
+    cmd.arg("--output=t,k")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("userId User-Id user-id");
+}
+
+#[test]
+fn output_ada_case() {
+    let mut cmd = naming();
+    cmd.arg("--output=a")
+        .write_stdin("snake_case")
+        .assert()
+        .success()
+        .stdout("snake_case Snake_Case");
+}
+
+#[test]
+fn output_cobol_case_in_regex() {
+    let mut cmd = naming();
+    cmd.args(["--output=C,s", "--regex"])
+        .write_stdin("customerId")
+        .assert()
+        .success()
+        .stdout("customerId CUSTOMER-ID|customer_id");
+}
+
+#[test]
+fn output_path_case() {
+    let mut cmd = naming();
+    cmd.arg("--output=/")
+        .write_stdin("user_profile_page")
+        .assert()
+        .success()
+        .stdout("user_profile_page user/profile/page");
+}
+
+#[test]
+fn unicode_words_in_json() {
+    let mut cmd = naming();
+    cmd.args(["--output=s,p", "--json"])
+        .write_stdin("straße_name nomRéseau")
+        .assert()
+        .success()
+        .stdout(
+            r#"{"result":[{"origin":"straße_name","snake":"straße_name","pascal":"StraßeName"},{"origin":"nomRéseau","snake":"nom_réseau","pascal":"NomRéseau"}]}"#,
+        );
+}
+
+#[test]
+fn output_dot_case_in_json() {
+    let mut cmd = naming();
+    cmd.arg("--output=d")
+        .arg("--json")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout(r#"{"result":[{"origin":"userId","dot":"user.id"}]}"#);
+}
+
+#[test]
+fn output_upper_flat_in_regex() {
+    let mut cmd = naming();
+    cmd.arg("--output=S,F")
+        .arg("--regex")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("userId USER_ID|USERID");
+}
+
+#[test]
+fn acronyms_option() {
+    let mut cmd = naming();
+    cmd.arg("--acronyms=url,id")
+        .arg("--output=c,p,s")
+        .write_stdin("parse_url_id")
+        .assert()
+        .success()
+        .stdout("parse_url_id parseURLID ParseURLID parse_url_id");
+}
+
+#[test]
+fn hungarian_prefixes_option() {
+    let mut cmd = naming();
+    cmd.arg("--filter=h")
+        .arg("--hungarian-prefixes=lpsz")
+        .arg("--output=s")
+        .write_stdin("lpszUserName")
+        .assert()
+        .success()
+        .stdout("UserName user_name");
+}
+
+#[test]
+fn null_flag_splits_stdin_into_records() {
+    let mut cmd = naming();
+    cmd.arg("--null")
+        .arg("--output=s")
+        .arg("--json")
+        .write_stdin("userId\0pageSize\0")
+        .assert()
+        .success()
+        .stdout(
+            r#"{"result":[{"origin":"userId","snake":"user_id"},"#.to_string()
+                + r#"{"origin":"pageSize","snake":"page_size"}]}"#,
+        );
+}
+
+#[test]
+fn replace_option() {
+    let mut cmd = naming();
+    cmd.arg("--replace=s")
+        .write_stdin("int fooBar = bazQux(a);\n")
+        .assert()
+        .success()
+        .stdout("int foo_bar = baz_qux(a);\n");
+}
+
+#[test]
+fn yaml_flag() {
+    let mut cmd = naming();
+    cmd.arg("--yaml")
+        .arg("--output=k,c")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout(
+            "result:\n  - origin: \"userId\"\n    kebab: \"user-id\"\n    camel: \"userId\"",
+        );
+}
+
+#[test]
+fn csv_flag() {
+    let mut cmd = naming();
+    cmd.arg("--csv")
+        .arg("--output=S,k")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("origin,screaming_snake,kebab\nuserId,USER_ID,user-id");
+}
+
+#[test]
+fn pretty_json() {
+    let mut cmd = naming();
+    cmd.arg("--json")
+        .arg("--pretty")
+        .arg("--output=s")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout(
+            r#"{
+  "result": [
+    {
+      "origin": "userId",
+      "snake": "user_id"
+    }
+  ]
+}"#,
+        );
+}
+
+#[test]
+fn count_flag() {
+    let mut cmd = naming();
+    cmd.arg("--count")
+        .arg("tests/data/all.txt")
+        .assert()
+        .success()
+        .stdout("camel: 1\nkebab: 1\npascal: 1\nscreaming_snake: 1\nsnake: 1");
+}
+
+#[test]
+fn stats_flag() {
+    let mut cmd = naming();
+    cmd.arg("--stats")
+        .args(["tests/data/all.txt", "tests/data/one.txt"])
+        .assert()
+        .success()
+        .stdout(
+            r#"{"stats":{"files":2,"bytes":65,"captures":6,"counts":{"camel":2,"kebab":1,"pascal":1,"screaming_snake":1,"snake":1}}}"#,
+        );
+}
+
+#[test]
+fn unique_flag() {
+    let mut cmd = naming();
+    cmd.arg("--unique")
+        .arg("--filter=h")
+        .arg("--output=s")
+        .write_stdin("intSize strSize")
+        .assert()
+        .success()
+        .stdout("Size size");
+}
+
+#[test]
+fn mark_unchanged_flag() {
+    let mut cmd = naming();
+    cmd.args(["--mark-unchanged", "--output=s,k"])
+        .write_stdin("page_size")
+        .assert()
+        .success()
+        .stdout("page_size page_size= page-size");
+}
+
+#[test]
+fn mark_unchanged_in_pretty_json() {
+    let mut cmd = naming();
+    cmd.args(["--mark-unchanged", "--output=s,c", "--json", "--pretty"])
+        .write_stdin("page_size")
+        .assert()
+        .success()
+        .stdout(
+            r#"{
+  "result": [
+    {
+      "origin": "page_size",
+      "snake": "page_size",
+      "camel": "pageSize",
+      "changed": {
+        "snake": false,
+        "camel": true
+      }
+    }
+  ]
+}"#,
+        );
+}
+
+#[test]
+fn mark_unchanged_in_yaml() {
+    let mut cmd = naming();
+    cmd.args(["--mark-unchanged", "--output=s,c", "--yaml"])
+        .write_stdin("page_size")
+        .assert()
+        .success()
+        .stdout(
+            "result:\n  \
+            - origin: \"page_size\"\n    \
+            snake: \"page_size\"\n    \
+            camel: \"pageSize\"\n    \
+            changed:\n      \
+            snake: false\n      \
+            camel: true",
+        );
+}
+
+#[test]
+fn mark_unchanged_in_csv() {
+    let mut cmd = naming();
+    cmd.args(["--mark-unchanged", "--output=s,c", "--csv"])
+        .write_stdin("page_size")
+        .assert()
+        .success()
+        .stdout("origin,snake,camel\npage_size,page_size=,pageSize");
+}
+
+#[test]
+fn mark_unchanged_in_table() {
+    let mut cmd = naming();
+    cmd.args(["--mark-unchanged", "--output=s,c", "--table"])
+        .write_stdin("page_size")
+        .assert()
+        .success()
+        .stdout(
+            "origin     snake       camel\npage_size  page_size=  pageSize",
+        );
+}
+
+#[test]
+fn limit_option() {
+    let mut cmd = naming();
+    cmd.args(["--unique", "--limit=2", "--output=s"])
+        .write_stdin("aB aB cD eF")
+        .assert()
+        .success()
+        .stdout("aB a_b\ncD c_d");
+}
+
+#[test]
+fn sort_flag() {
+    let mut cmd = naming();
+    cmd.arg("--sort")
+        .arg("--output=s")
+        .arg("tests/data/all.txt")
+        .assert()
+        .success()
+        .stdout(
+            "\
+PascalCase pascal_case
+SCREAMING_SNAKE screaming_snake
+camelCase camel_case
+kebab-case kebab_case
+snake_case snake_case",
+        );
+}
+
+#[test]
+fn regex_icase() {
+    let mut cmd = naming();
+    cmd.args(["--regex", "--regex-icase", "--output=S,s,c"])
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout(r"userId (?i)USER_ID|userId");
+}
+
+#[test]
+fn regex_word_boundary() {
+    let mut cmd = naming();
+    cmd.arg("--regex")
+        .arg("--regex-word-boundary")
+        .arg("--output=s,c")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout(r"userId \b(user_id|userId)\b");
+}
+
+#[test]
+fn show_rejected_in_json() {
+    let mut cmd = naming();
+    cmd.args(["--show-rejected", "--json", "--filter=s", "--output=k"])
+        .arg("tests/data/all.txt")
+        .assert()
+        .success()
+        .stdout(concat!(
+            r#"{"result":[{"origin":"snake_case","kebab":"snake-case"}],"#,
+            r#""rejected":["SCREAMING_SNAKE","kebab-case","camelCase","PascalCase"]}"#
+        ));
+}
+
+#[test]
+fn show_rejected_on_stderr() {
+    let mut cmd = naming();
+    cmd.args(["--show-rejected", "--filter=s,S,k,c", "--output=k"])
+        .arg("tests/data/all.txt")
+        .assert()
+        .success()
+        .stderr("naming: 1 word(s) rejected by filter: PascalCase\n");
+}
+
+#[test]
+fn only_ambiguous() {
+    let mut cmd = naming();
+    let output = cmd
+        .args(["--only-ambiguous", "--json"])
+        .write_stdin("ab-c abc some_word")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with(r#"{"result":[{"origin":"abc","candidates":["#));
+    assert!(!stdout.contains("ab-c"));
+    assert!(!stdout.contains("some_word"));
+}
+
+#[test]
+fn output_file() {
+    let path = std::env::temp_dir().join("naming_output_file_test.txt");
+    let mut cmd = naming();
+    cmd.arg("--output-file")
+        .arg(&path)
+        .arg("--output=s")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("");
+    let actual = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(actual, "userId user_id");
+}
+
+#[test]
+fn jobs_keeps_input_order() {
+    let mut cmd = naming();
+    cmd.args(["--jobs=2", "--output=s"])
+        .args(["tests/data/one.txt", "tests/data/all.txt"])
+        .assert()
+        .success()
+        .stdout(
+            "\
+userId user_id
+SCREAMING_SNAKE screaming_snake
+snake_case snake_case
+kebab-case kebab_case
+camelCase camel_case
+PascalCase pascal_case",
+        );
+}
+
+#[test]
+fn color_always() {
+    let mut cmd = naming();
+    cmd.args(["--color=always", "--output=s"])
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("\x1b[34muserId\x1b[0m \x1b[32muser_id\x1b[0m");
+}
+
+#[test]
+fn table() {
+    let mut cmd = naming();
+    cmd.args(["--table", "--output=s,p"])
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("origin  snake    pascal\nuserId  user_id  UserId");
+}
+
+#[test]
+fn invalid_only() {
+    let mut cmd = naming();
+    cmd.args(["--invalid-only", "--locator=regex:\\S+"])
+        .write_stdin("valid_word _invalid_ camelCase a__b")
+        .assert()
+        .success()
+        .stdout("_invalid_\na__b");
+}
+
+#[test]
+fn prefix_and_suffix() {
+    let mut cmd = naming();
+    cmd.args(["--output=s,S", "--prefix=get_", "--suffix=_t"])
+        .write_stdin("userName")
+        .assert()
+        .success()
+        .stdout("userName get_user_name_t get_USER_NAME_t");
+}
+
+#[test]
+fn strip_prefix() {
+    let mut cmd = naming();
+    cmd.args(["--strip-prefix=m_", "--output=s"])
+        .write_stdin("m_fooBar barBaz")
+        .assert()
+        .success()
+        .stdout("fooBar foo_bar\nbarBaz bar_baz");
+}
+
+#[test]
+fn strip_prefix_with_replace() {
+    let mut cmd = naming();
+    cmd.args(["--strip-prefix=m_", "-r", "snake"])
+        .write_stdin("m_fooBar = barBaz;\n")
+        .assert()
+        .success()
+        .stdout("m_foo_bar = bar_baz;\n");
+}
+
+#[test]
+fn completions() {
+    let mut cmd = naming();
+    let output = cmd.arg("--completions=bash").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("_naming()"));
+    assert!(stdout.contains("--output"));
+}
+
+#[test]
+fn version_verbose() {
+    let mut cmd = naming();
+    let output = cmd.arg("--version-verbose").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("naming "));
+    assert!(lines.contains(&"output S screaming_snake"));
+    assert!(lines.contains(&"locator string-literal"));
+}
+
+#[test]
+fn empty_filter_outputs_nothing() {
+    let mut cmd = naming();
+    cmd.arg("--filter=").write_stdin("foo").assert().success().stdout("");
+
+    let mut cmd = naming();
+    cmd.args(["--filter=", "--json"])
+        .write_stdin("foo")
+        .assert()
+        .success()
+        .stdout(r#"{"result":[]}"#);
+}
+
+#[test]
+fn empty_input_outputs_nothing() {
+    let mut cmd = naming();
+    cmd.write_stdin("").assert().success().stdout("");
+
+    let mut cmd = naming();
+    cmd.arg("--json")
+        .write_stdin("")
+        .assert()
+        .success()
+        .stdout(r#"{"result":[]}"#);
+}
+
+/// The binary under test, reading the config file in given directory
+/// only (the one under `XDG_CONFIG_HOME` is never found there).
+fn naming_with_config(dir: &std::path::Path, config: &str) -> Command {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("naming.toml"), config).unwrap();
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.current_dir(dir).env("XDG_CONFIG_HOME", dir);
+    cmd
+}
+
+#[test]
+fn config_file() {
+    let dir = std::env::temp_dir().join("naming_config_file_test");
+    let config = "output = [\"s\", \"k\"]\n";
+
+    naming_with_config(&dir, config)
+        .write_stdin("pageSize")
+        .assert()
+        .success()
+        .stdout("pageSize page_size page-size");
+
+    // explicit options override the file.
+    naming_with_config(&dir, config)
+        .arg("--output=S")
+        .write_stdin("pageSize")
+        .assert()
+        .success()
+        .stdout("pageSize PAGE_SIZE");
+
+    naming_with_config(&dir, config)
+        .arg("--no-config")
+        .write_stdin("pageSize")
+        .assert()
+        .success()
+        .stdout("pageSize PAGE_SIZE page_size page-size pageSize PageSize");
+}
+
+#[test]
+fn config_file_options_conflicting_with_command_line_are_dropped() {
+    let dir = std::env::temp_dir().join("naming_config_conflict_test");
+    let config = "output = [\"s\", \"k\"]\njson = true\n";
+
+    naming_with_config(&dir, config)
+        .args(["--replace", "s"])
+        .write_stdin("pageSize")
+        .assert()
+        .success()
+        .stdout("page_size");
+
+    naming_with_config(&dir, config)
+        .arg("--yaml")
+        .write_stdin("pageSize")
+        .assert()
+        .success()
+        .stdout(
+            "result:\n  - origin: \"pageSize\"\n    snake: \"page_size\"\n    kebab: \"page-size\"",
+        );
+}
+
+#[test]
+fn config_file_list_values_are_separate_occurrences() {
+    let dir = std::env::temp_dir().join("naming_config_list_test");
+    let config = "include = [\"one.txt\", \"two.txt\"]\noutput = \"s\"\n";
+
+    naming_with_config(&dir, config)
+        .arg(std::env::current_dir().unwrap().join("tests/data"))
+        .assert()
+        .success()
+        .stdout("pageSize page_size\nuserId user_id");
+}
+
+#[test]
+fn json_input_format() {
+    let mut cmd = naming();
+    cmd.args(["--input-format=json", "--output=k"])
+        .write_stdin(
+            r#"{"result":[{"origin":"pageSize","snake":"page_size"}]}"#,
+        )
+        .assert()
+        .success()
+        .stdout("pageSize page-size");
+}
+
+#[test]
+fn already_option() {
+    let mut cmd = naming();
+    cmd.args(["--already=snake", "--output=s"])
+        .write_stdin("pageSize page_size USER_NAME")
+        .assert()
+        .success()
+        .stdout("pageSize page_size\nUSER_NAME user_name");
+}
+
+#[test]
+fn words_flag() {
+    let mut cmd = naming();
+    cmd.arg("--words")
+        .write_stdin("getUserName page-size")
+        .assert()
+        .success()
+        .stdout("get\nuser\nname\n\npage\nsize");
+}
+
+#[test]
+fn dry_run_flag() {
+    let mut cmd = naming();
+    cmd.args(["--dry-run", "--filter=c", "--replace=s"])
+        .write_stdin("pageSize user_name userId pageSize")
+        .assert()
+        .success()
+        .stdout("2");
+}
+
+#[test]
+fn skip_binary_files_by_default() {
+    let mut cmd = naming();
+    cmd.args(["--output=s", "tests/data/binary.bin", "tests/data/one.txt"])
+        .assert()
+        .success()
+        .stdout("userId user_id")
+        .stderr(
+            "naming: tests/data/binary.bin: skipped, not a UTF-8 text file.\n\
+            naming: 1 binary file(s) skipped.\n",
+        );
+}
+
+#[test]
+fn walk_directory_with_include_and_max_depth() {
+    let mut cmd = naming();
+    cmd.args(["--output=s", "--filter=c", "--include=*.txt", "tests/data"])
+        .assert()
+        .success()
+        .stdout("camelCase camel_case\npageSize page_size\nuserId user_id");
+
+    let mut cmd = naming();
+    cmd.args(["--output=s", "--include=*.txt", "--exclude=all.txt"])
+        .args(["--max-depth=1", "tests/data"])
+        .assert()
+        .success()
+        .stdout("userId user_id");
+}
+
+#[test]
+fn trailing_newline_flags() {
+    let mut cmd = naming();
+    cmd.args(["--output=s", "--trailing-newline"])
+        .write_stdin("pageSize")
+        .assert()
+        .success()
+        .stdout("pageSize page_size\n");
+
+    // the last one wins.
+    let mut cmd = naming();
+    cmd.args(["--output=s", "--trailing-newline", "--no-trailing-newline"])
+        .write_stdin("pageSize")
+        .assert()
+        .success()
+        .stdout("pageSize page_size");
+}
+
+#[test]
+fn interactive_mode_converts_each_line() {
+    let mut cmd = naming();
+    cmd.args(["--interactive", "--output=s,k"])
+        .write_stdin("pageSize\n\nuserId -invalid_\n")
+        .assert()
+        .success()
+        .stdout("pageSize page_size page-size\nuserId user_id user-id\n");
+}
+
+#[test]
+fn diff_with_target() {
+    let mut cmd = naming();
+    cmd.args(["--diff", "--target=snake"])
+        .write_stdin("pageSize user_id UserName")
+        .assert()
+        .success()
+        .stdout("pageSize -> page_size\nUserName -> user_name");
+
+    let mut cmd = naming();
+    cmd.args(["--diff", "--target=s", "--json"])
+        .write_stdin("user_id UserName")
+        .assert()
+        .success()
+        .stdout(r#"{"result":[{"origin":"UserName","from":"pascal","to":"user_name"}]}"#);
+}
+
+#[test]
+fn acronym_style_option() {
+    let mut cmd = naming();
+    // only formats that capitalize words follow the style.
+    cmd.args(["--acronym-style=upper", "--output=c,p,t"])
+        .write_stdin("parseURLValue")
+        .assert()
+        .success()
+        .stdout("parseURLValue parseURLValue ParseURLValue Parse-URL-Value");
+
+    let mut cmd = naming();
+    cmd.args(["--acronym-style=lower", "--output=c,p,t"])
+        .write_stdin("parseURLValue")
+        .assert()
+        .success()
+        .stdout("parseURLValue parseurlValue ParseurlValue Parseurl-Value");
+}
+
+#[test]
+fn quiet_flag_silences_notices() {
+    let mut cmd = naming();
+    cmd.args(["-q", "--show-rejected", "--output=s"])
+        .args(["tests/data/binary.bin", "tests/data/one.txt"])
+        .assert()
+        .success()
+        .stdout("userId user_id")
+        .stderr("");
+}
+
+#[test]
+fn strict_flag_exit_codes() {
+    let mut cmd = naming();
+    cmd.args(["--strict", "--filter=S"])
+        .write_stdin("pageSize")
+        .assert()
+        .code(2)
+        .stdout("");
+
+    let mut cmd = naming();
+    cmd.args(["--strict", "--output=s"])
+        .write_stdin("pageSize")
+        .assert()
+        .code(0)
+        .stdout("pageSize page_size");
+
+    // empty results without --strict still succeed.
+    let mut cmd = naming();
+    cmd.arg("--filter=S").write_stdin("pageSize").assert().code(0);
+}
+
+#[test]
+fn abbreviations_option() {
+    let mut cmd = naming();
+    cmd.args(["--abbreviations=tests/data/abbreviations.conf", "--output=S,c"])
+        .write_stdin("config_path errorMessage")
+        .assert()
+        .success()
+        .stdout(
+            "config_path CFG_PATH cfgPath\nerrorMessage ERROR_MSG errorMsg",
+        );
+}
+
+#[cfg(feature = "sample")]
+#[test]
+fn sample_option_with_seed() {
+    let output = || {
+        let mut cmd = naming();
+        cmd.args(["--sample=2", "--seed=7", "--output=s"])
+            .write_stdin("aB cD eF gH iJ")
+            .output()
+            .unwrap()
+            .stdout
+    };
+    let sampled = String::from_utf8(output()).unwrap();
+    assert_eq!(sampled.lines().count(), 2);
+    assert_eq!(output(), sampled.as_bytes());
+}
+
+#[test]
+fn strict_case_warns_mixed_cases() {
+    let mut cmd = naming();
+    cmd.args(["--strict-case", "--output=s"])
+        .write_stdin("foo_barBaz pageSize foo_barBaz foo_bar-baz")
+        .assert()
+        .success()
+        .stdout("pageSize page_size")
+        .stderr(
+            "naming: mixed case `foo_barBaz`: \
+            separated segments with camel humps: `barBaz`.\n\
+            naming: mixed case `foo_bar-baz`: separators `_`, `-` are mixed.\n",
+        );
+}
+
+#[test]
+fn split_digits_locator() {
+    let mut cmd = naming();
+    cmd.args(["--locator=split-digits", "--output=S"])
+        .write_stdin("config2json3yaml")
+        .assert()
+        .success()
+        .stdout("config CONFIG\njson JSON\nyaml YAML");
+}
+
+#[test]
+fn custom_output_format() {
+    let mut cmd = naming();
+    cmd.args(["--output=custom:-:upper,custom:.:title,c"])
+        .write_stdin("page_size")
+        .assert()
+        .success()
+        .stdout("page_size PAGE-SIZE Page.Size pageSize");
+}
+
+#[test]
+fn no_header_flag() {
+    let mut cmd = naming();
+    cmd.args(["--csv", "--no-header", "--output=S,k"])
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("userId,USER_ID,user-id");
+
+    let mut cmd = naming();
+    cmd.args(["--table", "--no-header", "--header", "--output=s"])
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("origin  snake\nuserId  user_id");
+}
+
+#[test]
+fn crlf_line_endings_are_not_captured() {
+    let mut cmd = naming();
+    cmd.args([r"--locator=regex:(?m)^(\S+)$", "--output=s"])
+        .write_stdin("pageSize\r\nuserId\r\n")
+        .assert()
+        .success()
+        .stdout("pageSize page_size\nuserId user_id");
+}
+
+#[test]
+fn explain_flag() {
+    let mut cmd = naming();
+    cmd.arg("--explain").write_stdin("PAGE_SIZE").assert().success().stdout(
+        "PAGE_SIZE
+  case: screaming_snake
+  words: page, size
+  also valid: -
+  not snake: has uppercase letters
+  not kebab: has uppercase letters
+  not camel: starts with an uppercase letter
+  not pascal: has separators",
+    );
+}
+
+#[test]
+fn merge_acronyms_flag() {
+    let mut cmd = naming();
+    cmd.args(["--merge-acronyms", "--output=s,k"])
+        .write_stdin("HTTPSConnection IOError parseJSONData")
+        .assert()
+        .success()
+        .stdout(
+            "HTTPSConnection https_connection https-connection\n\
+            IOError io_error io-error\n\
+            parseJSONData parse_json_data parse-json-data",
+        );
+}
+
+#[test]
+fn digits_stay_with_previous_words() {
+    let mut cmd = naming();
+    cmd.args(["--output=s,c"])
+        .write_stdin("parse2Json utf8Decoder getHTTP2Response VERSION_2_CASE")
+        .assert()
+        .success()
+        .stdout(
+            "parse2Json parse2_json parse2Json\n\
+            utf8Decoder utf8_decoder utf8Decoder\n\
+            getHTTP2Response get_http2_response getHttp2Response\n\
+            VERSION_2_CASE version2_case version2Case",
+        );
+}
+
+#[test]
+fn acronyms_are_one_word_by_default() {
+    let mut cmd = naming();
+    cmd.args(["--output=s"])
+        .write_stdin("HTTPServer getHTTPSPage XMLHttpRequest HTTP2Server")
+        .assert()
+        .success()
+        .stdout(
+            "HTTPServer http_server\n\
+            getHTTPSPage get_https_page\n\
+            XMLHttpRequest xml_http_request\n\
+            HTTP2Server http2_server",
+        );
+}
+
+#[test]
+fn json_keys_option() {
+    let mut cmd = naming();
+    cmd.args(["--json", "--json-keys=short", "--output=S,k"])
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout(
+            r#"{"result":[{"origin":"userId","S":"USER_ID","k":"user-id"}]}"#,
+        );
+}
+
+#[test]
+fn timing_reports_stages_to_stderr() {
+    let mut cmd = naming();
+    let output = cmd
+        .args(["--timing", "--output=s"])
+        .write_stdin("pageSize")
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "pageSize page_size");
+    let stages: Vec<String> = String::from_utf8(output.stderr)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let line = line.strip_prefix("naming: timing: ").unwrap();
+            line.split_whitespace().next().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(stages, ["read", "capture", "filter", "conversion"]);
+
+    for (option, expect) in [
+        ("--dry-run", vec!["read", "capture", "filter"]),
+        ("--replace=s", vec!["read", "replace"]),
+    ] {
+        let mut cmd = naming();
+        let output =
+            cmd.args(["--timing", option]).write_stdin("pageSize").output();
+        let stderr = String::from_utf8(output.unwrap().stderr).unwrap();
+        let stages: Vec<&str> = stderr
+            .lines()
+            .map(|line| line.split_whitespace().nth(2).unwrap())
+            .collect();
+        assert_eq!(stages, expect);
+    }
+}
+
+#[test]
+fn qualified_locator() {
+    let mut cmd = naming();
+    cmd.args(["--locator=qualified", "--output=s,d"])
+        .write_stdin("myObj.getName(ns::Type);")
+        .assert()
+        .success()
+        .stdout("myObj.getName my_obj.get_name my.obj.get.name\nns::Type ns::type ns::type");
+}
+
+#[test]
+fn path_locator() {
+    let mut cmd = naming();
+    cmd.args(["--locator=path", "--output=s,c"])
+        .write_stdin("user/profile/page")
+        .assert()
+        .success()
+        .stdout("user/profile/page user_profile_page userProfilePage");
+}
+
+#[test]
+fn ndjson_output() {
+    let mut cmd = naming();
+    cmd.args(["--ndjson", "--output=S", "--json-keys=short"])
+        .write_stdin("pageSize user_id")
+        .assert()
+        .success()
+        .stdout(
+            "{\"origin\":\"pageSize\",\"S\":\"PAGE_SIZE\"}\n\
+            {\"origin\":\"user_id\",\"S\":\"USER_ID\"}",
+        );
+}
+
+#[test]
+fn fuzzy_filter() {
+    let mut cmd = naming();
+    cmd.args(["--filter=s", "--fuzzy-filter=1", "--output=c"])
+        .write_stdin("user_Id Page_Size")
+        .assert()
+        .success()
+        .stdout("user_id userId");
+}
+
+#[test]
+fn skip_comments_locator() {
+    let mut cmd = naming();
+    cmd.args(["--locator=skip-comments:python", "--output=c"])
+        .write_stdin("page_size = \"#user_id\"  # max_size\n")
+        .assert()
+        .success()
+        .stdout("page_size pageSize\nuser_id userId");
+}
+
+#[test]
+fn camel_filter_rejects_single_words() {
+    let mut cmd = naming();
+    cmd.args(["--filter=c", "--output=s"])
+        .write_stdin("count pageSize")
+        .assert()
+        .success()
+        .stdout("pageSize page_size");
+}
+
+#[cfg(feature = "normalize")]
+#[test]
+fn normalize_full_width_identifiers() {
+    let mut cmd = naming();
+    cmd.args(["--normalize", "--output=s"])
+        .write_stdin("ｆｏｏBar")
+        .assert()
+        .success()
+        .stdout("fooBar foo_bar");
+}
+
+#[test]
+fn mixed_split_locator() {
+    let mut cmd = naming();
+    cmd.args(["--locator=mixed-split", "--output=s"])
+        .write_stdin("XMLData_parser-v2")
+        .assert()
+        .success()
+        .stdout("XML xml\nData data\nparser parser\nv2 v2");
+}
+
+#[test]
+fn regex_wrap() {
+    let mut cmd = naming();
+    cmd.args(["--regex", "--wrap=18", "--output=S,s,c"])
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("userId (?x)\n  USER_ID|user_id|\n  userId");
+}
+
+#[test]
+fn files_from_stdin() {
+    let mut cmd = naming();
+    cmd.args(["--files-from", "-", "--output=s"])
+        .write_stdin("tests/data/one.txt\n\ntests/data/nested/two.txt\n")
+        .assert()
+        .success()
+        .stdout("userId user_id\npageSize page_size");
+}
+
+#[test]
+fn files_from_nul_delimited_list() {
+    let mut cmd = naming();
+    cmd.args(["--files-from=-", "--null", "--output=s"])
+        .write_stdin("tests/data/one.txt\0")
+        .assert()
+        .success()
+        .stdout("userId user_id");
+}
+
+#[test]
+fn output_by_format_names() {
+    let mut cmd = naming();
+    cmd.arg("--output=screaming-snake,s,pascal")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout("userId USER_ID user_id UserId");
+}
+
+#[test]
+fn group_by_case() {
+    let mut cmd = naming();
+    cmd.args(["--group-by-case", "--output=s"])
+        .write_stdin("pageSize user_id userName")
+        .assert()
+        .success()
+        .stdout(
+            "\
+== camel ==
+pageSize page_size
+userName user_name
+
+== snake ==
+user_id user_id",
+        );
+}
+
+#[test]
+fn replace_and_sort_by_format_names() {
+    let mut cmd = naming();
+    cmd.args(["-r", "snake"])
+        .write_stdin("int fooBar = bazQux(a);\n")
+        .assert()
+        .success()
+        .stdout("int foo_bar = baz_qux(a);\n");
+
+    let mut cmd = naming();
+    cmd.args(["--sort-by", "screaming-snake", "--output=s"])
+        .write_stdin("b_c aB")
+        .assert()
+        .success()
+        .stdout("aB a_b\nb_c b_c");
+}
+
+#[test]
+fn sort_by_converted_strings_with_abbreviations() {
+    // "cfg_path" < "ch_path" < "config_path".
+    let mut cmd = naming();
+    cmd.args([
+        "--sort-by=s",
+        "--abbreviations=tests/data/abbreviations.conf",
+        "--prefix=m_",
+        "--output=s",
+    ])
+    .write_stdin("ch_path config_path")
+    .assert()
+    .success()
+    .stdout("config_path m_cfg_path\nch_path m_ch_path");
+}
+
+#[test]
+fn replace_and_sort_by_custom_formats() {
+    let mut cmd = naming();
+    cmd.args(["-r", "custom:-:upper"])
+        .write_stdin("fooBar = bazQux;\n")
+        .assert()
+        .success()
+        .stdout("FOO-BAR = BAZ-QUX;\n");
+
+    let mut cmd = naming();
+    cmd.args(["--sort-by", "custom:.:lower", "--output=s"])
+        .write_stdin("bA aB")
+        .assert()
+        .success()
+        .stdout("aB a_b\nbA b_a");
+}